@@ -0,0 +1,222 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, LitStr};
+
+// Options of the `#[appsync(...)]` attributes of the type, its variants and their fields
+#[derive(Default)]
+struct AppsyncAttributes {
+    error_type: Option<LitStr>,
+    info: bool,
+}
+impl AppsyncAttributes {
+    fn parse(attrs: &[Attribute], allow_type: bool, allow_info: bool) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("appsync")) {
+            attr.parse_nested_meta(|meta| {
+                if allow_type && meta.path.is_ident("type") {
+                    attributes.error_type = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if allow_info && meta.path.is_ident("info") {
+                    attributes.info = true;
+                    Ok(())
+                } else {
+                    let expected = if allow_type {
+                        "`type = \"...\"`"
+                    } else {
+                        "`info`"
+                    };
+                    Err(meta.error(format!("Unknown option, expected {expected}")))
+                }
+            })?;
+        }
+        Ok(attributes)
+    }
+}
+
+// A struct or an enum variant
+struct ErrorVariant {
+    // `None` for a struct
+    ident: Option<Ident>,
+    error_type: LitStr,
+    fields: Fields,
+    // Index of the `#[appsync(info)]` field
+    info_field: Option<usize>,
+}
+impl ErrorVariant {
+    fn new(
+        ident: Option<Ident>,
+        attrs: &[Attribute],
+        default_type: LitStr,
+        fields: Fields,
+    ) -> syn::Result<Self> {
+        let error_type = AppsyncAttributes::parse(attrs, true, false)?
+            .error_type
+            .unwrap_or(default_type);
+        let mut info_field = None;
+        for (index, field) in fields.iter().enumerate() {
+            if AppsyncAttributes::parse(&field.attrs, false, true)?.info {
+                if info_field.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "Only one field can be marked with `#[appsync(info)]`",
+                    ));
+                }
+                info_field = Some(index);
+            }
+        }
+        Ok(Self {
+            ident,
+            error_type,
+            fields,
+            info_field,
+        })
+    }
+    // `Self::Variant { .. }` or `Self { .. }`, binding the info field to `info`
+    fn pattern(&self) -> TokenStream2 {
+        let path = match self.ident {
+            Some(ref ident) => quote! {Self::#ident},
+            None => quote! {Self},
+        };
+        match self.fields {
+            Fields::Named(ref fields) => match self.info_field {
+                Some(index) => {
+                    let name = fields.named[index].ident.as_ref().expect("named field");
+                    quote! {#path { #name: info, .. }}
+                }
+                None => quote! {#path { .. }},
+            },
+            Fields::Unnamed(ref fields) => {
+                let bindings = (0..fields.unnamed.len()).map(|index| {
+                    if Some(index) == self.info_field {
+                        quote! {info}
+                    } else {
+                        quote! {_}
+                    }
+                });
+                quote! {#path(#(#bindings),*)}
+            }
+            Fields::Unit => path,
+        }
+    }
+    fn error_type_match_arm(&self) -> TokenStream2 {
+        let pattern = self.pattern();
+        let error_type = &self.error_type;
+        quote! {#pattern => #error_type}
+    }
+    fn error_info_match_arm(&self) -> TokenStream2 {
+        let pattern = self.pattern();
+        if self.info_field.is_some() {
+            quote! {#pattern => ::core::option::Option::Some(::lambda_appsync::res_to_json(info))}
+        } else {
+            quote! {#pattern => ::core::option::Option::None}
+        }
+    }
+}
+
+struct AppsyncErrorDerive {
+    input: DeriveInput,
+    variants: Vec<ErrorVariant>,
+}
+impl TryFrom<DeriveInput> for AppsyncErrorDerive {
+    type Error = syn::Error;
+
+    fn try_from(input: DeriveInput) -> Result<Self, Self::Error> {
+        let variants = match input.data {
+            Data::Struct(ref data) => {
+                let default_type = LitStr::new(&input.ident.to_string(), input.ident.span());
+                vec![ErrorVariant::new(
+                    None,
+                    &input.attrs,
+                    default_type,
+                    data.fields.clone(),
+                )?]
+            }
+            Data::Enum(ref data) => {
+                // The type of the enum is the default type of its variants
+                let default_type = AppsyncAttributes::parse(&input.attrs, true, false)?.error_type;
+                let mut variants = vec![];
+                let mut errors = vec![];
+                for variant in data.variants.iter() {
+                    let default_type = default_type.clone().unwrap_or_else(|| {
+                        LitStr::new(&variant.ident.to_string(), variant.ident.span())
+                    });
+                    match ErrorVariant::new(
+                        Some(variant.ident.clone()),
+                        &variant.attrs,
+                        default_type,
+                        variant.fields.clone(),
+                    ) {
+                        Ok(variant) => variants.push(variant),
+                        Err(e) => errors.push(e),
+                    }
+                }
+                if let Some(error) = errors.into_iter().reduce(|mut acc, e| {
+                    acc.combine(e);
+                    acc
+                }) {
+                    return Err(error);
+                }
+                variants
+            }
+            Data::Union(ref data) => {
+                return Err(syn::Error::new(
+                    data.union_token.span,
+                    "AppsyncError cannot be derived for unions",
+                ));
+            }
+        };
+        Ok(Self { input, variants })
+    }
+}
+impl ToTokens for AppsyncErrorDerive {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let name = &self.input.ident;
+        let (impl_generics, type_generics, _) = self.input.generics.split_for_impl();
+        // The message of the error is its `Display` implementation, which may have its own bounds
+        let mut kind_generics = self.input.generics.clone();
+        kind_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! {Self: ::std::fmt::Display});
+        let kind_where_clause = &kind_generics.where_clause;
+        let mut from_generics = self.input.generics.clone();
+        from_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! {#name #type_generics: ::lambda_appsync::AppsyncErrorKind});
+        let from_where_clause = &from_generics.where_clause;
+        let error_type_match_arms = self.variants.iter().map(ErrorVariant::error_type_match_arm);
+        let error_info_match_arms = self.variants.iter().map(ErrorVariant::error_info_match_arm);
+        tokens.extend(quote! {
+            impl #impl_generics ::lambda_appsync::AppsyncErrorKind for #name #type_generics #kind_where_clause {
+                fn error_type(&self) -> &str {
+                    match self {
+                        #(#error_type_match_arms,)*
+                    }
+                }
+                fn error_message(&self) -> ::std::string::String {
+                    ::std::string::ToString::to_string(self)
+                }
+                fn error_info(&self) -> ::core::option::Option<::lambda_appsync::serde_json::Value> {
+                    match self {
+                        #(#error_info_match_arms,)*
+                    }
+                }
+            }
+            impl #impl_generics ::core::convert::From<#name #type_generics> for ::lambda_appsync::AppsyncError #from_where_clause {
+                fn from(error: #name #type_generics) -> Self {
+                    ::lambda_appsync::AppsyncError::from_error_kind(&error)
+                }
+            }
+        });
+    }
+}
+
+pub(crate) fn appsync_error_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match AppsyncErrorDerive::try_from(input) {
+        Ok(derive) => derive.into_token_stream().into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
@@ -1,9 +1,10 @@
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 
-use graphql_parser::schema::{Definition, Document, TypeDefinition};
+use graphql_parser::schema::{Definition, Document, TypeDefinition, TypeExtension};
 use proc_macro2::Span;
-use quote::{quote, quote_spanned, ToTokens};
-use syn::spanned::Spanned;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{ext::IdentExt, spanned::Spanned};
 
 use crate::common::{Name, OperationKind};
 
@@ -82,10 +83,13 @@ impl ToTokens for Scalar {
 
 enum FieldType {
     Overriden(syn::Type),
-    Custom { name: Name },
+    // Boxed when the type is recursive, to keep a finite size
+    Custom { name: Name, boxed: bool },
     Scalar(Scalar),
     List(Box<FieldType>),
     Optionnal(Box<FieldType>),
+    // Nullable input telling an omitted value from `null`, with `nullable_inputs_as_maybe`
+    Maybe(Box<FieldType>),
 }
 impl FieldType {
     fn from_string(name: String) -> Self {
@@ -93,19 +97,186 @@ impl FieldType {
             Self::Scalar(scalar)
         } else {
             let name = Name::from((name, current_span()));
-            Self::Custom { name }
+            Self::Custom { name, boxed: false }
         }
     }
     fn is_optionnal(&self) -> bool {
         matches!(self, FieldType::Optionnal(_))
     }
+    fn is_maybe(&self) -> bool {
+        matches!(self, FieldType::Maybe(_))
+    }
+    fn set_maybe(&mut self) {
+        if let FieldType::Optionnal(field_type) = self {
+            // The placeholder is only there while the inner type moves to the new variant
+            let field_type =
+                std::mem::replace(field_type, Box::new(FieldType::Scalar(Scalar::Boolean)));
+            *self = FieldType::Maybe(field_type);
+        }
+    }
+    // Original name of the type this one contains by value, i.e. not through a `Vec`
+    fn contained_type(&self) -> Option<&str> {
+        match self {
+            FieldType::Custom { name, .. } => Some(name.orig()),
+            FieldType::Optionnal(field_type) | FieldType::Maybe(field_type) => {
+                field_type.contained_type()
+            }
+            FieldType::Overriden(_) | FieldType::Scalar(_) | FieldType::List(_) => None,
+        }
+    }
+    // Original name of the schema type this one is made of, through the lists
+    fn named_type(&self) -> Option<&str> {
+        match self {
+            FieldType::Custom { name, .. } => Some(name.orig()),
+            FieldType::Optionnal(field_type)
+            | FieldType::Maybe(field_type)
+            | FieldType::List(field_type) => field_type.named_type(),
+            FieldType::Overriden(_) | FieldType::Scalar(_) => None,
+        }
+    }
+    fn is_boxed(&self) -> bool {
+        match self {
+            FieldType::Custom { boxed, .. } => *boxed,
+            FieldType::Optionnal(field_type) | FieldType::Maybe(field_type) => {
+                field_type.is_boxed()
+            }
+            FieldType::Overriden(_) | FieldType::Scalar(_) | FieldType::List(_) => false,
+        }
+    }
+    fn set_boxed(&mut self) {
+        match self {
+            FieldType::Custom { boxed, .. } => *boxed = true,
+            FieldType::Optionnal(field_type) | FieldType::Maybe(field_type) => {
+                field_type.set_boxed()
+            }
+            FieldType::Overriden(_) | FieldType::Scalar(_) | FieldType::List(_) => (),
+        }
+    }
+    fn required(&self) -> &Self {
+        match self {
+            FieldType::Optionnal(field_type) | FieldType::Maybe(field_type) => field_type,
+            field_type => field_type,
+        }
+    }
+    // Type returned by the interface trait accessors: nullable fields are borrowed as
+    // `Option<&T>` and boxed values are borrowed without their box
+    fn accessor_type(&self) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let borrowed = |field_type: &FieldType| match field_type {
+            FieldType::Custom { name, boxed: true } => {
+                let name = name.to_type_ident();
+                quote_spanned! {span=>&#name}
+            }
+            field_type => quote_spanned! {span=>&#field_type},
+        };
+        match self {
+            FieldType::Optionnal(field_type) => {
+                let borrowed = borrowed(field_type);
+                quote_spanned! {span=>::core::option::Option<#borrowed>}
+            }
+            field_type => borrowed(field_type),
+        }
+    }
     fn override_type(&mut self, type_override: TypeOverride) {
         match self {
             FieldType::Overriden(_) | FieldType::Custom { .. } | FieldType::Scalar(_) => {
                 *self = FieldType::Overriden(type_override.type_ident())
             }
             FieldType::List(field_type) => field_type.override_type(type_override),
-            FieldType::Optionnal(field_type) => field_type.override_type(type_override),
+            FieldType::Optionnal(field_type) | FieldType::Maybe(field_type) => {
+                field_type.override_type(type_override)
+            }
+        }
+    }
+    fn apply_scalar_maps(&mut self, scalar_maps: &super::ScalarMaps) {
+        match self {
+            FieldType::Custom { name, .. } => {
+                if let Some(scalar_map) = scalar_maps.get(name.orig()) {
+                    *self = FieldType::Overriden(scalar_map.type_ident().clone())
+                }
+            }
+            // Explicit type overrides take precedence over scalar maps
+            FieldType::Overriden(_) | FieldType::Scalar(_) => (),
+            FieldType::List(field_type) => field_type.apply_scalar_maps(scalar_maps),
+            FieldType::Optionnal(field_type) | FieldType::Maybe(field_type) => {
+                field_type.apply_scalar_maps(scalar_maps)
+            }
+        }
+    }
+}
+// GraphQL type of a payload, without its non-null markers as AppSync does not compare them
+fn payload_type(field_type: &graphql_parser::schema::Type<'_, String>) -> String {
+    match field_type {
+        graphql_parser::query::Type::NamedType(name) => name.clone(),
+        graphql_parser::query::Type::ListType(inner) => format!("[{}]", payload_type(inner)),
+        graphql_parser::query::Type::NonNullType(inner) => payload_type(inner),
+    }
+}
+// GraphQL type as declared in the schema, unaffected by overrides, for the `check_responses` option
+enum GraphQLType {
+    Named(String),
+    List(Box<GraphQLType>),
+    NonNull(Box<GraphQLType>),
+}
+impl From<&graphql_parser::schema::Type<'_, String>> for GraphQLType {
+    fn from(value: &graphql_parser::schema::Type<'_, String>) -> Self {
+        match value {
+            graphql_parser::query::Type::NamedType(name) => Self::Named(name.clone()),
+            graphql_parser::query::Type::ListType(inner) => {
+                Self::List(Box::new(Self::from(inner.as_ref())))
+            }
+            graphql_parser::query::Type::NonNullType(inner) => {
+                Self::NonNull(Box::new(Self::from(inner.as_ref())))
+            }
+        }
+    }
+}
+impl std::fmt::Display for GraphQLType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Named(name) => write!(f, "{name}"),
+            Self::List(inner) => write!(f, "[{inner}]"),
+            Self::NonNull(inner) => write!(f, "{inner}!"),
+        }
+    }
+}
+// The `lambda_appsync::OutputType` of the GraphQL types, referring to the statics generated for
+// the object types in the `__output_types` module
+struct OutputTypes<'a> {
+    objects: HashSet<&'a str>,
+    // Object types of the unions and interfaces
+    abstracts: HashMap<&'a str, Vec<&'a str>>,
+}
+impl OutputTypes<'_> {
+    fn to_tokens(&self, graphql_type: &GraphQLType) -> proc_macro2::TokenStream {
+        let span = current_span();
+        match graphql_type {
+            GraphQLType::NonNull(inner) => {
+                let inner = self.to_tokens(inner);
+                quote_spanned! {span=>::lambda_appsync::OutputType::NonNull(&#inner)}
+            }
+            GraphQLType::List(inner) => {
+                let inner = self.to_tokens(inner);
+                quote_spanned! {span=>::lambda_appsync::OutputType::List(&#inner)}
+            }
+            GraphQLType::Named(name) if self.objects.contains(name.as_str()) => {
+                let object_ident = format_ident!("{}", name, span = span);
+                quote_spanned! {span=>::lambda_appsync::OutputType::Object(&__output_types::#object_ident)}
+            }
+            GraphQLType::Named(name) => match self.abstracts.get(name.as_str()) {
+                Some(types) => {
+                    let object_idents = types
+                        .iter()
+                        .map(|type_name| format_ident!("{}", type_name, span = span));
+                    quote_spanned! {span=>
+                        ::lambda_appsync::OutputType::Abstract {
+                            name: #name,
+                            types: &[#(&__output_types::#object_idents),*],
+                        }
+                    }
+                }
+                None => quote_spanned! {span=>::lambda_appsync::OutputType::Leaf(#name)},
+            },
         }
     }
 }
@@ -137,45 +308,568 @@ impl ToTokens for FieldType {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let span = current_span();
         match self {
-            FieldType::Custom { name } => {
+            FieldType::Custom { name, boxed } => {
                 let name = name.to_type_ident();
-                tokens.extend(quote_spanned! {span=>#name})
+                if *boxed {
+                    tokens.extend(quote_spanned! {span=>::std::boxed::Box<#name>})
+                } else {
+                    tokens.extend(quote_spanned! {span=>#name})
+                }
             }
             FieldType::Scalar(scalar) => tokens.extend(quote_spanned! {span=>#scalar}),
             FieldType::List(field_type) => tokens.extend(quote_spanned! {span=>Vec<#field_type>}),
             FieldType::Optionnal(field_type) => {
                 tokens.extend(quote_spanned! {span=>::core::option::Option<#field_type>})
             }
+            FieldType::Maybe(field_type) => {
+                tokens.extend(quote_spanned! {span=>::lambda_appsync::Maybe<#field_type>})
+            }
             FieldType::Overriden(ty) => tokens.extend(quote_spanned! {span=>#ty}),
         }
     }
 }
 
+// GraphQL description of a schema element, emitted as rustdoc on the generated item
+#[derive(Debug, Default)]
+struct Description(Option<String>);
+impl From<Option<String>> for Description {
+    fn from(value: Option<String>) -> Self {
+        Self(value.filter(|d| !d.trim().is_empty()))
+    }
+}
+impl ToTokens for Description {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        if let Some(ref description) = self.0 {
+            let span = current_span();
+            let lines = description.lines().map(|l| format!(" {l}"));
+            tokens.extend(quote_spanned! {span=>
+                #(#[doc = #lines])*
+            });
+        }
+    }
+}
+
+// Reason of a GraphQL `@deprecated` directive, emitted as `#[deprecated]` on the generated item
+#[derive(Debug, Default)]
+struct Deprecation(Option<String>);
+impl Deprecation {
+    fn is_deprecated(&self) -> bool {
+        self.0.is_some()
+    }
+    fn reason(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+}
+impl From<&[graphql_parser::schema::Directive<'_, String>]> for Deprecation {
+    fn from(directives: &[graphql_parser::schema::Directive<'_, String>]) -> Self {
+        Self(directives.iter().find(|d| d.name == "deprecated").map(|d| {
+            d.arguments
+                .iter()
+                .find_map(|(name, value)| match value {
+                    graphql_parser::schema::Value::String(reason) if name == "reason" => {
+                        Some(reason.clone())
+                    }
+                    _ => None,
+                })
+                // Default reason of the GraphQL specification
+                .unwrap_or_else(|| "No longer supported".to_owned())
+        }))
+    }
+}
+impl ToTokens for Deprecation {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        if let Some(ref reason) = self.0 {
+            let span = current_span();
+            tokens.extend(quote_spanned! {span=>
+                #[deprecated(note = #reason)]
+            });
+        }
+    }
+}
+
+// The serde derives use the variants they are given without allowing the `deprecated` lint,
+// so enums with deprecated variants are defined in a private module allowing it and re-exported.
+// The module sees the items of its parent, which the `derive` and `attr` paths may refer to.
+fn allow_deprecated_variants(
+    enum_name: &proc_macro2::Ident,
+    enum_definition: proc_macro2::TokenStream,
+    has_deprecated_variants: bool,
+) -> proc_macro2::TokenStream {
+    if !has_deprecated_variants {
+        return enum_definition;
+    }
+    let span = current_span();
+    let module_name = format_ident!(
+        "__deprecated_{}",
+        enum_name.to_string().to_lowercase(),
+        span = span
+    );
+    quote_spanned! {span=>
+        #[allow(deprecated)]
+        mod #module_name {
+            #[allow(unused_imports)]
+            use super::*;
+            #enum_definition
+        }
+        pub use #module_name::#enum_name;
+    }
+}
+
+// Constant GraphQL value, kept without the lifetime of the parsed schema
+#[derive(Debug)]
+enum ConstValue {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    Enum(String),
+    Null,
+    List(Vec<ConstValue>),
+    Object(Vec<(String, ConstValue)>),
+}
+impl From<&graphql_parser::schema::Value<'_, String>> for ConstValue {
+    fn from(value: &graphql_parser::schema::Value<'_, String>) -> Self {
+        use graphql_parser::schema::Value;
+        match value {
+            Value::Int(number) => {
+                Self::Int(number.as_i64().expect("graphql_parser integers fit in i64"))
+            }
+            Value::Float(number) => Self::Float(*number),
+            Value::String(string) => Self::String(string.clone()),
+            Value::Enum(string) => Self::Enum(string.clone()),
+            Value::Boolean(boolean) => Self::Boolean(*boolean),
+            // Variables cannot appear in a schema
+            Value::Null | Value::Variable(_) => Self::Null,
+            Value::List(values) => Self::List(values.iter().map(Self::from).collect()),
+            Value::Object(fields) => Self::Object(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), Self::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+impl ToTokens for ConstValue {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = current_span();
+        tokens.extend(match self {
+            Self::Int(number) => {
+                let number = proc_macro2::Literal::i64_unsuffixed(*number);
+                quote_spanned! {span=>#number}
+            }
+            Self::Float(number) => {
+                let number = proc_macro2::Literal::f64_unsuffixed(*number);
+                quote_spanned! {span=>#number}
+            }
+            Self::String(string) | Self::Enum(string) => quote_spanned! {span=>#string},
+            Self::Boolean(boolean) => quote_spanned! {span=>#boolean},
+            Self::Null => quote_spanned! {span=>null},
+            Self::List(values) => quote_spanned! {span=>[#(#values),*]},
+            Self::Object(fields) => {
+                let names = fields.iter().map(|(name, _)| name);
+                let values = fields.iter().map(|(_, value)| value);
+                quote_spanned! {span=>{#(#names: #values),*}}
+            }
+        });
+    }
+}
+
+// GraphQL default value of an argument or an input field, emitted as a `serde_json::json!` value
+#[derive(Debug, Default)]
+struct DefaultValue {
+    value: Option<ConstValue>,
+    // Known to deserialize into the Rust type of its argument or field, see `DefaultValueChecker`
+    checked: bool,
+}
+impl DefaultValue {
+    fn is_set(&self) -> bool {
+        self.value.is_some()
+    }
+    fn is_checked(&self) -> bool {
+        self.is_set() && self.checked
+    }
+}
+impl From<Option<graphql_parser::schema::Value<'_, String>>> for DefaultValue {
+    fn from(value: Option<graphql_parser::schema::Value<'_, String>>) -> Self {
+        // A `null` default is what is extracted anyway when the value is omitted
+        Self {
+            value: value
+                .filter(|v| !matches!(v, graphql_parser::schema::Value::Null))
+                .map(|v| ConstValue::from(&v)),
+            checked: false,
+        }
+    }
+}
+impl ToTokens for DefaultValue {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        if let Some(ref value) = self.value {
+            let span = current_span();
+            tokens.extend(quote_spanned! {span=>
+                ::lambda_appsync::serde_json::json!(#value)
+            });
+        }
+    }
+}
+
+// Checks the default values against the Rust types they are deserialized into, so that a
+// mismatch is a compile error instead of a panic of `Default` or the builders
+struct DefaultValueChecker<'a> {
+    enums: &'a [Enum],
+    inputs: Vec<&'a Structure>,
+}
+impl<'a> DefaultValueChecker<'a> {
+    // Nested input defaults are checked recursively, this only bounds cyclic ones
+    const MAX_DEPTH: usize = 32;
+
+    fn new(enums: &'a [Enum], structures: &'a [Structure]) -> Self {
+        Self {
+            enums,
+            inputs: structures.iter().filter(|s| s.is_input).collect(),
+        }
+    }
+    // `Ok(false)` when the value can only be checked during deserialization, for overridden
+    // types and scalars parsed from strings
+    fn check(&self, value: &ConstValue, field_type: &FieldType) -> Result<bool, String> {
+        self.check_at_depth(value, field_type, 0)
+    }
+    fn check_at_depth(
+        &self,
+        value: &ConstValue,
+        field_type: &FieldType,
+        depth: usize,
+    ) -> Result<bool, String> {
+        if depth > Self::MAX_DEPTH {
+            return Ok(false);
+        }
+        let mismatch = || Err(format!("unexpected value `{}`", value.to_token_stream()));
+        match (field_type, value) {
+            (FieldType::Overriden(_), _) => Ok(false),
+            (FieldType::Optionnal(_) | FieldType::Maybe(_), ConstValue::Null) => Ok(true),
+            (FieldType::Optionnal(field_type) | FieldType::Maybe(field_type), value) => {
+                self.check_at_depth(value, field_type, depth)
+            }
+            (FieldType::List(field_type), ConstValue::List(values)) => {
+                values.iter().try_fold(true, |checked, value| {
+                    Ok(self.check_at_depth(value, field_type, depth)? && checked)
+                })
+            }
+            (FieldType::Scalar(scalar), value) => match (scalar, value) {
+                (Scalar::Int, ConstValue::Int(number)) if i32::try_from(*number).is_ok() => {
+                    Ok(true)
+                }
+                (Scalar::Float, ConstValue::Int(_) | ConstValue::Float(_))
+                | (Scalar::Boolean, ConstValue::Boolean(_))
+                | (
+                    Scalar::String
+                    | Scalar::AWSEmail
+                    | Scalar::AWSPhone
+                    | Scalar::AWSDate
+                    | Scalar::AWSTime
+                    | Scalar::AWSDateTime
+                    | Scalar::AWSURL,
+                    ConstValue::String(_),
+                )
+                | (Scalar::AWSJSON, _) => Ok(true),
+                (Scalar::AWSTimestamp, ConstValue::Int(number)) if *number >= 0 => Ok(true),
+                (Scalar::ID | Scalar::AWSIPAddress, ConstValue::String(_)) => Ok(false),
+                _ => mismatch(),
+            },
+            (FieldType::Custom { name, .. }, value) => {
+                if let Some(r#enum) = self.enums.iter().find(|e| e.name.orig() == name.orig()) {
+                    match value {
+                        ConstValue::Enum(variant)
+                            if r#enum.variants.iter().any(|v| v.name.orig() == variant) =>
+                        {
+                            Ok(true)
+                        }
+                        _ => mismatch(),
+                    }
+                } else if let Some(input) =
+                    self.inputs.iter().find(|s| s.name.orig() == name.orig())
+                {
+                    let ConstValue::Object(values) = value else {
+                        return mismatch();
+                    };
+                    self.check_input(input, values, depth)
+                } else {
+                    Ok(false)
+                }
+            }
+            _ => mismatch(),
+        }
+    }
+    fn check_input(
+        &self,
+        input: &Structure,
+        values: &[(String, ConstValue)],
+        depth: usize,
+    ) -> Result<bool, String> {
+        if let Some((name, _)) = values
+            .iter()
+            .find(|(name, _)| !input.fields.iter().any(|f| f.name.orig() == name))
+        {
+            return Err(format!("`{}` has no field `{name}`", input.name.orig()));
+        }
+        input.fields.iter().try_fold(true, |checked, field| {
+            let value = values
+                .iter()
+                .find(|(name, _)| name == field.name.orig())
+                .map(|(_, value)| value)
+                .or(field.default_value.value.as_ref());
+            let field_checked = match value {
+                Some(value) => self
+                    .check_at_depth(value, &field.field_type, depth + 1)
+                    .map_err(|e| format!("field `{}`: {e}", field.name.orig()))?,
+                None if field.field_type.is_optionnal() || field.field_type.is_maybe() => true,
+                None => {
+                    return Err(format!(
+                        "missing non-null field `{}` of `{}`",
+                        field.name.orig(),
+                        input.name.orig()
+                    ))
+                }
+            };
+            Ok(field_checked && checked)
+        })
+    }
+}
+
+// AppSync authorization directives of a type or a field, as `AppsyncAuthMode` variant names
+// and the Cognito groups of the `cognito_groups` arguments
+#[derive(Debug, Default, Clone)]
+struct AuthDirectives {
+    modes: Vec<&'static str>,
+    cognito_groups: Vec<String>,
+}
+impl AuthDirectives {
+    fn is_empty(&self) -> bool {
+        self.modes.is_empty()
+    }
+    // Directives of a field replace those of its type
+    fn or(self, type_auth: &Self) -> Self {
+        if self.is_empty() {
+            type_auth.clone()
+        } else {
+            self
+        }
+    }
+    fn modes_tokens(&self) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let modes = self
+            .modes
+            .iter()
+            .map(|mode| format_ident!("{mode}", span = span));
+        quote_spanned! {span=>
+            &[#(::lambda_appsync::AppsyncAuthMode::#modes),*]
+        }
+    }
+    fn cognito_groups_tokens(&self) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let cognito_groups = self.cognito_groups.iter();
+        quote_spanned! {span=>
+            &[#(#cognito_groups),*]
+        }
+    }
+}
+impl From<&[graphql_parser::schema::Directive<'_, String>]> for AuthDirectives {
+    fn from(directives: &[graphql_parser::schema::Directive<'_, String>]) -> Self {
+        let mut auth = Self::default();
+        for directive in directives {
+            let mode = match directive.name.as_str() {
+                "aws_api_key" => "ApiKey",
+                "aws_iam" => "Iam",
+                "aws_oidc" => "Oidc",
+                "aws_cognito_user_pools" | "aws_auth" => "CognitoUserPools",
+                "aws_lambda" => "Lambda",
+                _ => continue,
+            };
+            if !auth.modes.contains(&mode) {
+                auth.modes.push(mode);
+            }
+            let groups = directive
+                .arguments
+                .iter()
+                .filter(|(name, _)| name == "cognito_groups")
+                .flat_map(|(_, value)| match value {
+                    graphql_parser::schema::Value::List(groups) => groups.as_slice(),
+                    _ => &[],
+                });
+            for group in groups {
+                if let graphql_parser::schema::Value::String(group) = group {
+                    if !auth.cognito_groups.contains(group) {
+                        auth.cognito_groups.push(group.clone());
+                    }
+                }
+            }
+        }
+        auth
+    }
+}
+
+// Derives and attributes added to a generated type with the `derive`, `derive_all` and `attr` options
+#[derive(Default)]
+struct ExtraAttributes {
+    derives: Vec<syn::Path>,
+    attrs: Vec<syn::Attribute>,
+}
+impl ExtraAttributes {
+    // Derives already emitted or implemented by the generated code are skipped, as well as
+    // derives given several times
+    fn add_derives<'a>(
+        &mut self,
+        derives: impl IntoIterator<Item = &'a syn::Path>,
+        generated_derives: &[&str],
+    ) {
+        for derive in derives {
+            let name = derive
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+                .unwrap_or_default();
+            // Paths are compared as tokens, `syn` is built without its `extra-traits` feature
+            let tokens = derive.to_token_stream().to_string();
+            if generated_derives.contains(&name.as_str())
+                || self
+                    .derives
+                    .iter()
+                    .any(|d| d.to_token_stream().to_string() == tokens)
+            {
+                continue;
+            }
+            self.derives.push(derive.clone());
+        }
+    }
+    fn add(&mut self, type_attributes: &super::TypeAttributes, generated_derives: &[&str]) {
+        self.add_derives(type_attributes.derives(), generated_derives);
+        self.attrs.extend(type_attributes.attrs().iter().cloned());
+    }
+}
+// `syn` is built without its `extra-traits` feature
+impl std::fmt::Debug for ExtraAttributes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_token_stream().to_string())
+    }
+}
+impl ToTokens for ExtraAttributes {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = current_span();
+        if !self.derives.is_empty() {
+            let derives = self.derives.iter();
+            tokens.extend(quote_spanned! {span=>
+                #[derive(#(#derives),*)]
+            });
+        }
+        for attr in self.attrs.iter() {
+            attr.to_tokens(tokens);
+        }
+    }
+}
+
+// Mutations of the `@aws_subscribe(mutations: [...])` directive of a subscription
+#[derive(Debug, Default)]
+struct SubscribedMutations(Vec<String>);
+impl SubscribedMutations {
+    fn contains(&self, mutation_name: &str) -> bool {
+        self.0.iter().any(|m| m == mutation_name)
+    }
+}
+impl From<&[graphql_parser::schema::Directive<'_, String>]> for SubscribedMutations {
+    fn from(directives: &[graphql_parser::schema::Directive<'_, String>]) -> Self {
+        let mut mutations = vec![];
+        let values = directives
+            .iter()
+            .filter(|d| d.name == "aws_subscribe")
+            .flat_map(|d| d.arguments.iter())
+            .filter(|(name, _)| name == "mutations")
+            .flat_map(|(_, value)| match value {
+                graphql_parser::schema::Value::List(values) => values.as_slice(),
+                _ => &[],
+            });
+        for value in values {
+            if let graphql_parser::schema::Value::String(mutation_name) = value {
+                if !mutations.contains(mutation_name) {
+                    mutations.push(mutation_name.clone());
+                }
+            }
+        }
+        Self(mutations)
+    }
+}
+
 struct Field {
     name: Name,
     field_type: FieldType,
+    graphql_type: GraphQLType,
+    description: Description,
+    deprecation: Deprecation,
+    default_value: DefaultValue,
 }
 impl From<graphql_parser::schema::Field<'_, String>> for Field {
     fn from(value: graphql_parser::schema::Field<'_, String>) -> Self {
         let name = Name::from((value.name, current_span()));
+        let graphql_type = GraphQLType::from(&value.field_type);
         let field_type = FieldType::from(value.field_type);
-        Self { name, field_type }
+        let description = Description::from(value.description);
+        let deprecation = Deprecation::from(value.directives.as_slice());
+        Self {
+            name,
+            field_type,
+            graphql_type,
+            description,
+            deprecation,
+            default_value: DefaultValue::default(),
+        }
     }
 }
 impl From<graphql_parser::schema::InputValue<'_, String>> for Field {
     fn from(value: graphql_parser::schema::InputValue<'_, String>) -> Self {
         let name = Name::from((value.name, current_span()));
+        let graphql_type = GraphQLType::from(&value.value_type);
         let field_type = FieldType::from(value.value_type);
-        Self { name, field_type }
+        let description = Description::from(value.description);
+        let deprecation = Deprecation::from(value.directives.as_slice());
+        let default_value = DefaultValue::from(value.default_value);
+        Self {
+            name,
+            field_type,
+            graphql_type,
+            description,
+            deprecation,
+            default_value,
+        }
+    }
+}
+impl Field {
+    // Name of the function returning the default value of an input field, for `Default` and
+    // the builders
+    fn default_fct_ident(&self) -> proc_macro2::Ident {
+        format_ident!(
+            "__default_{}",
+            self.name.to_var_ident(),
+            span = current_span()
+        )
     }
 }
 
 struct FieldContext<'a> {
     field: &'a Field,
+    // Field of the struct an input type with default values is deserialized from,
+    // where the fields with a default value are kept as JSON
+    deserialized: bool,
 }
 impl<'a> FieldContext<'a> {
     fn new(field: &'a Field) -> Self {
-        Self { field }
+        Self {
+            field,
+            deserialized: false,
+        }
+    }
+    fn deserialized(field: &'a Field) -> Self {
+        Self {
+            field,
+            deserialized: true,
+        }
     }
 }
 impl ToTokens for FieldContext<'_> {
@@ -193,9 +887,41 @@ impl ToTokens for FieldContext<'_> {
                 rename = #orig_name
             });
         }
+        if self.deserialized {
+            if field.default_value.is_set() || field_type.is_optionnal() || field_type.is_maybe() {
+                serde_options.push(quote_spanned! {span=>
+                    default
+                });
+            }
+            let field_type = if field.default_value.is_set() {
+                quote_spanned! {span=>::lambda_appsync::Maybe<::lambda_appsync::serde_json::Value>}
+            } else {
+                field_type.to_token_stream()
+            };
+            if !serde_options.is_empty() {
+                tokens.extend(quote_spanned! {span=>
+                    #[serde(#(#serde_options),*)]
+                })
+            }
+            tokens.extend(quote_spanned! {span=>
+                #name: #field_type
+            });
+            return;
+        }
+        field.description.to_tokens(tokens);
+        field.deprecation.to_tokens(tokens);
+        if field_type.is_optionnal() || field_type.is_maybe() {
+            serde_options.push(quote_spanned! {span=>
+                default
+            });
+        }
         if field_type.is_optionnal() {
             serde_options.push(quote_spanned! {span=>
-                default, skip_serializing_if = "Option::is_none"
+                skip_serializing_if = "Option::is_none"
+            });
+        } else if field_type.is_maybe() {
+            serde_options.push(quote_spanned! {span=>
+                skip_serializing_if = "::lambda_appsync::Maybe::is_undefined"
             });
         }
         if !serde_options.is_empty() {
@@ -209,57 +935,673 @@ impl ToTokens for FieldContext<'_> {
     }
 }
 
+// Applies the `type_override = Type.field: CustomType` options of a type to its fields,
+// `kind` names the fields in the error messages ("field", "member")
+fn apply_field_type_overrides(
+    fields: &mut [Field],
+    mut type_overrides: super::FieldTypeOverrides,
+    kind: &str,
+) -> Result<(), syn::Error> {
+    let mut errors = vec![];
+    for field in fields.iter_mut() {
+        let field_name = field.name.orig();
+        if let Some((field_override, args_override)) = type_overrides.remove(field_name) {
+            if !args_override.is_empty() {
+                errors.extend(args_override.into_values().flat_map(|to| {
+                    syn::Error::new(
+                        to.arg_name().expect("always set in args_override").span(),
+                        "Using args overrides is only supported on operations",
+                    )
+                }));
+            }
+            if let Some(field_override) = field_override {
+                field.field_type.override_type(field_override);
+            }
+        }
+    }
+    if !type_overrides.is_empty() {
+        errors.extend(
+            type_overrides
+                .into_values()
+                .flat_map(|to| to.0.into_iter().chain(to.1.into_values()))
+                .map(|to| {
+                    syn::Error::new(
+                        to.field_name().span(),
+                        format!("No {kind} `{}` in `{}`", to.field_name(), to.type_name()),
+                    )
+                }),
+        );
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors
+            .into_iter()
+            .reduce(|mut acc, e| {
+                acc.combine(e);
+                acc
+            })
+            .expect("not empty"))
+    }
+}
+// Applies the `name_override = Type: CustomName` and `name_override = Type.field: custom_name`
+// options of a type to its name and fields, `kind` names the fields in the error messages
+fn apply_field_name_overrides(
+    name: &mut Name,
+    fields: &mut [Field],
+    (type_override, mut field_overrides): super::TypeNameOverride,
+    kind: &str,
+) -> Result<(), syn::Error> {
+    if let Some(type_override) = type_override {
+        name.override_name(type_override.new_name());
+    }
+    for field in fields.iter_mut() {
+        let field_name = field.name.orig();
+        if let Some(field_override) = field_overrides.remove(field_name) {
+            field.name.override_name(field_override.new_name());
+        }
+    }
+    if field_overrides.is_empty() {
+        return Ok(());
+    }
+    Err(field_overrides
+        .into_values()
+        .map(|no| {
+            syn::Error::new(
+                no.field_name()
+                    .expect("always set in field_overrides")
+                    .span(),
+                format!(
+                    "No {kind} `{}` in `{}`",
+                    no.field_name().expect("always set in field_overrides"),
+                    no.type_name()
+                ),
+            )
+        })
+        .reduce(|mut acc, e| {
+            acc.combine(e);
+            acc
+        })
+        .expect("not empty"))
+}
+
 struct Structure {
     name: Name,
+    description: Description,
     fields: Vec<Field>,
+    // Original names of the GraphQL interfaces implemented by this type
+    implements: Vec<String>,
+    // GraphQL `input` type
+    is_input: bool,
+    extra_attributes: ExtraAttributes,
+    // Generate a `<Struct>Builder` with the `builders` option
+    builder: bool,
+    // Returned by a query, a mutation or a field resolver, whose events select its fields
+    operation_result: bool,
+    // Field name enums of the fields whose type is another `type`, by field index, for the
+    // accessors of the `<Struct>SelectedFields` trait
+    nested_field_names: Vec<(usize, proc_macro2::Ident)>,
 }
 impl Structure {
     fn apply_type_overrides(
         &mut self,
-        mut type_overrides: super::FieldTypeOverrides,
+        type_overrides: super::FieldTypeOverrides,
     ) -> Result<(), syn::Error> {
-        let mut errors = vec![];
-        for field in self.fields.iter_mut() {
-            let field_name = field.name.orig();
-            if let Some((field_override, args_override)) = type_overrides.remove(field_name) {
-                if !args_override.is_empty() {
-                    errors.extend(args_override.into_values().flat_map(|to| {
-                        syn::Error::new(
-                            to.arg_name().expect("always set in args_override").span(),
-                            "Using args overrides is only supported on operations",
-                        )
-                    }));
+        apply_field_type_overrides(&mut self.fields, type_overrides, "field")
+    }
+    fn apply_name_overrides(
+        &mut self,
+        name_overrides: super::TypeNameOverride,
+    ) -> Result<(), syn::Error> {
+        apply_field_name_overrides(&mut self.name, &mut self.fields, name_overrides, "field")
+    }
+    // The fields resolved by the Lambda are not in the `source` the parent is deserialized from
+    fn set_resolved_fields_nullable(&mut self, resolved_fields: &Operations) {
+        for field in self.fields.iter_mut().filter(|f| {
+            !f.field_type.is_optionnal()
+                && resolved_fields
+                    .0
+                    .iter()
+                    .any(|op| op.name.orig() == f.name.orig())
+        }) {
+            // The placeholder is only there while the field type moves into the option
+            let field_type =
+                std::mem::replace(&mut field.field_type, FieldType::Scalar(Scalar::Boolean));
+            field.field_type = FieldType::Optionnal(Box::new(field_type));
+        }
+    }
+}
+impl From<graphql_parser::schema::ObjectType<'_, String>> for Structure {
+    fn from(value: graphql_parser::schema::ObjectType<'_, String>) -> Self {
+        let name = Name::from((value.name, current_span()));
+        let description = Description::from(value.description);
+        let fields = value.fields.into_iter().map(Field::from).collect();
+        let implements = value.implements_interfaces;
+        Self {
+            name,
+            description,
+            fields,
+            implements,
+            is_input: false,
+            extra_attributes: ExtraAttributes::default(),
+            builder: false,
+            operation_result: false,
+            nested_field_names: vec![],
+        }
+    }
+}
+impl From<graphql_parser::schema::InputObjectType<'_, String>> for Structure {
+    fn from(value: graphql_parser::schema::InputObjectType<'_, String>) -> Self {
+        let name = Name::from(value.name);
+        let description = Description::from(value.description);
+        let fields = value.fields.into_iter().map(Field::from).collect();
+        Self {
+            name,
+            description,
+            fields,
+            implements: vec![],
+            is_input: true,
+            extra_attributes: ExtraAttributes::default(),
+            builder: false,
+            operation_result: false,
+            nested_field_names: vec![],
+        }
+    }
+}
+impl ToTokens for Structure {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = current_span();
+        let struct_name = self.name.to_type_ident();
+        let description = &self.description;
+        let fields = self.fields.iter().map(FieldContext::new);
+        let extra_attributes = &self.extra_attributes;
+        let try_from = self.has_default_values().then(|| {
+            let deserialize_struct = self.deserialize_struct_ident().to_string();
+            quote_spanned! {span=>#[serde(try_from = #deserialize_struct)]}
+        });
+        tokens.extend(quote_spanned! {span=>
+            #description
+            #[derive(Debug, Clone, ::lambda_appsync::serde::Serialize, ::lambda_appsync::serde::Deserialize)]
+            #try_from
+            #extra_attributes
+            pub struct #struct_name {
+                #(#fields,)*
+            }
+        });
+        self.default_values_to_tokens(tokens);
+        self.boxed_accessors_to_tokens(tokens);
+        if self.builder {
+            self.builder_to_tokens(tokens);
+        }
+        if !self.is_input {
+            self.field_names_to_tokens(tokens);
+        }
+    }
+}
+impl Structure {
+    // Output types get a `<Struct>FieldName` enum to check their selected fields,
+    // its variants follow the GraphQL names as `name_override` gives snake case names
+    fn field_names_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = current_span();
+        let struct_name = self.name.to_type_ident();
+        let enum_name = self.field_name_enum_ident();
+        let variants = self
+            .fields
+            .iter()
+            .map(|f| {
+                let variant = Name::from((f.name.orig().to_owned(), span)).to_type_ident();
+                // `Self` cannot be escaped, like `self` for the field names
+                if variant == "Self" {
+                    format_ident!("RSelf", span = span)
+                } else {
+                    variant
                 }
-                if let Some(field_override) = field_override {
-                    field.field_type.override_type(field_override);
+            })
+            .collect::<Vec<_>>();
+        let descriptions = self.fields.iter().map(|f| &f.description);
+        let orig_names = self.fields.iter().map(|f| f.name.orig());
+        let enum_doc = format!(
+            " The fields of [{struct_name}], as selected in a `lambda_appsync::SelectedFields`"
+        );
+        tokens.extend(quote_spanned! {span=>
+            #[doc = #enum_doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum #enum_name {
+                #(
+                    #descriptions
+                    #variants,
+                )*
+            }
+            impl ::lambda_appsync::SelectableField for #enum_name {
+                const ALL: &'static [Self] = &[#(Self::#variants),*];
+                fn name(self) -> &'static str {
+                    match self {
+                        #(Self::#variants => #orig_names,)*
+                    }
                 }
             }
+        });
+        // The selection set of an event is the one of the operation result, the nested types
+        // only get theirs through the `<Struct>SelectedFields` accessors
+        if self.operation_result {
+            let selected_fields_doc = format!(
+                " Returns the fields of [{struct_name}] selected by the request of `event`, \
+                which must be an operation returning [{struct_name}]"
+            );
+            tokens.extend(quote_spanned! {span=>
+                impl #struct_name {
+                    #[doc = #selected_fields_doc]
+                    pub fn selected_fields<O>(
+                        event: &::lambda_appsync::AppsyncEvent<O>,
+                    ) -> ::lambda_appsync::SelectedFields<#enum_name> {
+                        ::lambda_appsync::SelectedFields::new(event.info.selection_set())
+                    }
+                }
+            });
         }
-        if !type_overrides.is_empty() {
-            errors.extend(
-                type_overrides
-                    .into_values()
-                    .flat_map(|to| to.0.into_iter().chain(to.1.into_values()))
-                    .map(|to| {
-                        syn::Error::new(
-                            to.field_name().span(),
-                            format!("No field `{}` in `{}`", to.field_name(), to.type_name()),
+        self.selected_fields_trait_to_tokens(tokens, &variants);
+    }
+    // The fields whose type is another `type` get an accessor returning its selected fields,
+    // so that their field name enum is the one of that type
+    fn selected_fields_trait_to_tokens(
+        &self,
+        tokens: &mut proc_macro2::TokenStream,
+        variants: &[proc_macro2::Ident],
+    ) {
+        if self.nested_field_names.is_empty() {
+            return;
+        }
+        let span = current_span();
+        let struct_name = self.name.to_type_ident();
+        let enum_name = self.field_name_enum_ident();
+        let trait_name = self.selected_fields_trait_ident();
+        let trait_doc = format!(
+            " The selected fields of the nested types of [{struct_name}], from its \
+            `lambda_appsync::SelectedFields`"
+        );
+        let accessors = self
+            .nested_field_names
+            .iter()
+            .map(|(i, _)| self.fields[*i].name.to_var_ident())
+            .collect::<Vec<_>>();
+        let accessor_docs = self.nested_field_names.iter().map(|(i, _)| {
+            format!(
+                " Returns the selected fields of `{}`, none if it is not selected",
+                self.fields[*i].name.orig()
+            )
+        });
+        let accessor_deprecations = self
+            .nested_field_names
+            .iter()
+            .map(|(i, _)| &self.fields[*i].deprecation);
+        let nested_enum_names = self
+            .nested_field_names
+            .iter()
+            .map(|(_, nested_enum_name)| nested_enum_name)
+            .collect::<Vec<_>>();
+        let accessor_variants = self.nested_field_names.iter().map(|(i, _)| &variants[*i]);
+        tokens.extend(quote_spanned! {span=>
+            #[doc = #trait_doc]
+            pub trait #trait_name {
+                #(
+                    #[doc = #accessor_docs]
+                    #accessor_deprecations
+                    fn #accessors(&self) -> ::lambda_appsync::SelectedFields<#nested_enum_names>;
+                )*
+            }
+            #[allow(deprecated)]
+            impl #trait_name for ::lambda_appsync::SelectedFields<#enum_name> {
+                #(
+                    fn #accessors(&self) -> ::lambda_appsync::SelectedFields<#nested_enum_names> {
+                        ::lambda_appsync::SelectedFields::new(
+                            self.sub(#enum_name::#accessor_variants)
+                                .cloned()
+                                .unwrap_or_default(),
                         )
-                    }),
-            );
+                    }
+                )*
+            }
+        });
+    }
+    fn field_name_enum_ident(&self) -> proc_macro2::Ident {
+        format_ident!(
+            "{}FieldName",
+            self.name.to_type_ident(),
+            span = current_span()
+        )
+    }
+    fn selected_fields_trait_ident(&self) -> proc_macro2::Ident {
+        format_ident!(
+            "{}SelectedFields",
+            self.name.to_type_ident(),
+            span = current_span()
+        )
+    }
+    // The builder tracks its non-null fields in type parameters, `()` until they are set,
+    // so that `build` only exists once all of them are set
+    fn builder_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = current_span();
+        let struct_name = self.name.to_type_ident();
+        let builder_name = format_ident!("{}Builder", struct_name, span = span);
+        let field_names = self
+            .fields
+            .iter()
+            .map(|f| f.name.to_var_ident())
+            .collect::<Vec<_>>();
+        // Type parameter of each non-null field without a default value
+        let params = self
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| {
+                !(f.default_value.is_checked()
+                    || f.field_type.is_optionnal()
+                    || f.field_type.is_maybe())
+            })
+            .map(|(i, f)| (i, format_ident!("__F{}", i, span = span), &f.field_type))
+            .collect::<Vec<_>>();
+        let param_idents = params.iter().map(|(_, p, _)| p).collect::<Vec<_>>();
+        let param_position = |i: usize| params.iter().position(|(index, _, _)| *index == i);
+        let builder_field_types =
+            self.fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| match param_position(i) {
+                    Some(position) => param_idents[position].to_token_stream(),
+                    None => f.field_type.to_token_stream(),
+                });
+        let initial_values = self.fields.iter().enumerate().map(|(i, f)| {
+            if f.default_value.is_checked() {
+                let default_fct = f.default_fct_ident();
+                quote_spanned! {span=>#struct_name::#default_fct()}
+            } else if param_position(i).is_some() {
+                quote_spanned! {span=>()}
+            } else {
+                quote_spanned! {span=>::core::default::Default::default()}
+            }
+        });
+        let setters = self.fields.iter().enumerate().map(|(i, f)| {
+            let description = &f.description;
+            let deprecation = &f.deprecation;
+            let field_name = &field_names[i];
+            let field_type = &f.field_type;
+            let Some(position) = param_position(i) else {
+                return quote_spanned! {span=>
+                    #[allow(deprecated)]
+                    impl<#(#param_idents),*> #builder_name<#(#param_idents),*> {
+                        #description
+                        #deprecation
+                        pub fn #field_name(mut self, value: impl ::core::convert::Into<#field_type>) -> Self {
+                            self.#field_name = value.into();
+                            self
+                        }
+                    }
+                };
+            };
+            let other_params = param_idents
+                .iter()
+                .enumerate()
+                .filter(|(p, _)| *p != position)
+                .map(|(_, param)| param);
+            let unset_params = param_idents.iter().enumerate().map(|(p, param)| {
+                if p == position {
+                    quote_spanned! {span=>()}
+                } else {
+                    quote_spanned! {span=>#param}
+                }
+            });
+            let set_params = param_idents.iter().enumerate().map(|(p, param)| {
+                if p == position {
+                    quote_spanned! {span=>#field_type}
+                } else {
+                    quote_spanned! {span=>#param}
+                }
+            });
+            let moved_fields = field_names.iter().map(|name| {
+                if name == field_name {
+                    quote_spanned! {span=>#name: value.into()}
+                } else {
+                    quote_spanned! {span=>#name: self.#name}
+                }
+            });
+            quote_spanned! {span=>
+                #[allow(deprecated)]
+                impl<#(#other_params),*> #builder_name<#(#unset_params),*> {
+                    #description
+                    #deprecation
+                    pub fn #field_name(self, value: impl ::core::convert::Into<#field_type>) -> #builder_name<#(#set_params),*> {
+                        #builder_name {
+                            #(#moved_fields,)*
+                        }
+                    }
+                }
+            }
+        });
+        let set_types = params.iter().map(|(_, _, field_type)| field_type);
+        let builder_doc = format!(
+            " Builder of [{struct_name}], `build` is only available once all its non-null fields are set"
+        );
+        let builder_fn_doc =
+            format!(" Returns a builder of [{struct_name}], its nullable fields default to `None`");
+        tokens.extend(quote_spanned! {span=>
+            #[doc = #builder_doc]
+            #[must_use]
+            pub struct #builder_name<#(#param_idents = ()),*> {
+                #(#field_names: #builder_field_types,)*
+            }
+            #[allow(deprecated)]
+            impl #struct_name {
+                #[doc = #builder_fn_doc]
+                pub fn builder() -> #builder_name {
+                    #builder_name {
+                        #(#field_names: #initial_values,)*
+                    }
+                }
+            }
+            #(#setters)*
+            #[allow(deprecated)]
+            impl #builder_name<#(#set_types),*> {
+                #[doc = " Builds the value"]
+                pub fn build(self) -> #struct_name {
+                    #struct_name {
+                        #(#field_names: self.#field_names,)*
+                    }
+                }
+            }
+        });
+    }
+    // Boxed fields get an accessor borrowing their value
+    fn boxed_accessors_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = current_span();
+        let accessors = self
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let (wrapper, field_type) = match field.field_type {
+                    FieldType::Optionnal(ref field_type) => {
+                        (Some(quote! {::core::option::Option}), field_type.as_ref())
+                    }
+                    FieldType::Maybe(ref field_type) => {
+                        (Some(quote! {::lambda_appsync::Maybe}), field_type.as_ref())
+                    }
+                    ref field_type => (None, field_type),
+                };
+                let FieldType::Custom {
+                    ref name,
+                    boxed: true,
+                } = *field_type
+                else {
+                    return None;
+                };
+                let accessor = field.name.to_var_ident();
+                let type_ident = name.to_type_ident();
+                let deprecation = &field.deprecation;
+                let doc = format!(
+                    " Borrows the `{}` field, boxed because `{}` is a recursive type",
+                    accessor.unraw(),
+                    name.orig()
+                );
+                Some(if let Some(wrapper) = wrapper {
+                    quote_spanned! {span=>
+                        #[doc = #doc]
+                        #deprecation
+                        pub fn #accessor(&self) -> #wrapper<&#type_ident> {
+                            self.#accessor.as_deref()
+                        }
+                    }
+                } else {
+                    quote_spanned! {span=>
+                        #[doc = #doc]
+                        #deprecation
+                        pub fn #accessor(&self) -> &#type_ident {
+                            &self.#accessor
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        if accessors.is_empty() {
+            return;
         }
-        if errors.is_empty() {
-            Ok(())
+        let struct_name = self.name.to_type_ident();
+        tokens.extend(quote_spanned! {span=>
+            #[allow(deprecated)]
+            impl #struct_name {
+                #(#accessors)*
+            }
+        });
+    }
+    // Derives of the `derive` options already emitted or implemented for this structure
+    fn generated_derives(&self) -> &'static [&'static str] {
+        if self.implements_default() {
+            &["Debug", "Clone", "Serialize", "Deserialize", "Default"]
         } else {
-            Err(errors
-                .into_iter()
-                .reduce(|mut acc, e| {
-                    acc.combine(e);
-                    acc
-                })
-                .expect("not empty"))
+            &["Debug", "Clone", "Serialize", "Deserialize"]
+        }
+    }
+    fn has_default_values(&self) -> bool {
+        self.fields.iter().any(|f| f.default_value.is_set())
+    }
+    // Struct deserialized before applying the default values, serde default functions cannot fail
+    fn deserialize_struct_ident(&self) -> proc_macro2::Ident {
+        format_ident!(
+            "__{}Deserialize",
+            self.name.to_type_ident(),
+            span = current_span()
+        )
+    }
+    // The default values that cannot be checked at compile time are left out of `Default` and
+    // the builders, as if the fields had none
+    fn implements_default(&self) -> bool {
+        self.fields.iter().any(|f| f.default_value.is_checked())
+            && self.fields.iter().all(|f| {
+                f.default_value.is_checked()
+                    || f.field_type.is_optionnal()
+                    || f.field_type.is_maybe()
+            })
+    }
+    // Input types with default values are deserialized from a struct keeping these fields as
+    // JSON, so that a default value not matching a `type_override` is an error. They also get
+    // the default functions of the fields whose default value is checked and, when every field
+    // has one or is nullable, a `Default` implementation
+    fn default_values_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        if !self.has_default_values() {
+            return;
+        }
+        let defaulted_fields = self
+            .fields
+            .iter()
+            .filter(|f| f.default_value.is_checked())
+            .collect::<Vec<_>>();
+        let span = current_span();
+        let struct_name = self.name.to_type_ident();
+        let deserialize_struct = self.deserialize_struct_ident();
+        let deserialized_fields = self.fields.iter().map(FieldContext::deserialized);
+        let field_names = self
+            .fields
+            .iter()
+            .map(|f| f.name.to_var_ident())
+            .collect::<Vec<_>>();
+        let deserialized_values = self.fields.iter().map(|f| {
+            let name = f.name.to_var_ident();
+            if f.default_value.is_set() {
+                let orig_name = f.name.orig();
+                let default_value = &f.default_value;
+                quote_spanned! {span=>
+                    ::lambda_appsync::input_field_from_json_or(value.#name, #orig_name, #default_value)?
+                }
+            } else {
+                quote_spanned! {span=>value.#name}
+            }
+        });
+        tokens.extend(quote_spanned! {span=>
+            #[doc(hidden)]
+            #[derive(::lambda_appsync::serde::Deserialize)]
+            pub struct #deserialize_struct {
+                #(#deserialized_fields,)*
+            }
+            #[allow(deprecated)]
+            impl ::core::convert::TryFrom<#deserialize_struct> for #struct_name {
+                type Error = ::std::string::String;
+                fn try_from(value: #deserialize_struct) -> ::core::result::Result<Self, Self::Error> {
+                    ::core::result::Result::Ok(Self {
+                        #(#field_names: #deserialized_values,)*
+                    })
+                }
+            }
+        });
+        let default_fcts = defaulted_fields.iter().map(|f| f.default_fct_ident());
+        let default_types = defaulted_fields.iter().map(|f| &f.field_type);
+        let default_values = defaulted_fields.iter().map(|f| &f.default_value);
+        tokens.extend(quote_spanned! {span=>
+            impl #struct_name {
+                #(
+                    fn #default_fcts() -> #default_types {
+                        ::lambda_appsync::serde_json::from_value(#default_values)
+                            .expect("checked against the type at compile time")
+                    }
+                )*
+            }
+        });
+        if self.implements_default() {
+            let field_names = self.fields.iter().map(|f| f.name.to_var_ident());
+            let field_values = self.fields.iter().map(|f| {
+                if f.default_value.is_checked() {
+                    let default_fct = f.default_fct_ident();
+                    quote_spanned! {span=>Self::#default_fct()}
+                } else {
+                    quote_spanned! {span=>::core::default::Default::default()}
+                }
+            });
+            tokens.extend(quote_spanned! {span=>
+                #[allow(deprecated)]
+                impl ::core::default::Default for #struct_name {
+                    fn default() -> Self {
+                        Self {
+                            #(#field_names: #field_values,)*
+                        }
+                    }
+                }
+            });
         }
     }
+}
+
+#[derive(Debug)]
+struct EnumVariant {
+    name: Name,
+    description: Description,
+    deprecation: Deprecation,
+}
+
+#[derive(Debug)]
+struct Enum {
+    name: Name,
+    description: Description,
+    variants: Vec<EnumVariant>,
+    extra_attributes: ExtraAttributes,
+}
+impl Enum {
     fn apply_name_overrides(
         &mut self,
         (type_override, mut field_overrides): super::TypeNameOverride,
@@ -268,10 +1610,10 @@ impl Structure {
         if let Some(type_override) = type_override {
             self.name.override_name(type_override.new_name());
         }
-        for field in self.fields.iter_mut() {
-            let field_name = field.name.orig();
-            if let Some(field_override) = field_overrides.remove(field_name) {
-                field.name.override_name(field_override.new_name());
+        for variant in self.variants.iter_mut() {
+            let variant_name = variant.name.orig();
+            if let Some(field_override) = field_overrides.remove(variant_name) {
+                variant.name.override_name(field_override.new_name());
             }
         }
         if !field_overrides.is_empty() {
@@ -281,7 +1623,7 @@ impl Structure {
                         .expect("always set in field_overrides")
                         .span(),
                     format!(
-                        "No field `{}` in `{}`",
+                        "No variant `{}` in `{}`",
                         no.field_name().expect("always set in field_overrides"),
                         no.type_name()
                     ),
@@ -301,67 +1643,370 @@ impl Structure {
         }
     }
 }
-impl From<graphql_parser::schema::ObjectType<'_, String>> for Structure {
-    fn from(value: graphql_parser::schema::ObjectType<'_, String>) -> Self {
+impl From<graphql_parser::schema::EnumType<'_, String>> for Enum {
+    fn from(value: graphql_parser::schema::EnumType<'_, String>) -> Self {
         let name = Name::from((value.name, current_span()));
-        let fields = value.fields.into_iter().map(Field::from).collect();
-        Self { name, fields }
+        let description = Description::from(value.description);
+        let variants = value
+            .values
+            .into_iter()
+            .map(|v| EnumVariant {
+                deprecation: Deprecation::from(v.directives.as_slice()),
+                name: Name::from((v.name, current_span())),
+                description: Description::from(v.description),
+            })
+            .collect();
+        Self {
+            name,
+            description,
+            variants,
+            extra_attributes: ExtraAttributes::default(),
+        }
     }
 }
-impl From<graphql_parser::schema::InputObjectType<'_, String>> for Structure {
-    fn from(value: graphql_parser::schema::InputObjectType<'_, String>) -> Self {
-        let name = Name::from(value.name);
-        let fields = value.fields.into_iter().map(Field::from).collect();
-        Self { name, fields }
+impl ToTokens for Enum {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let enum_name = self.name.to_type_ident();
+        let count = proc_macro2::Literal::usize_unsuffixed(self.variants.len());
+        let description = &self.description;
+        let variant_descriptions = self.variants.iter().map(|v| &v.description);
+        let variant_deprecations = self.variants.iter().map(|v| &v.deprecation);
+        let variant_orig_iter = self
+            .variants
+            .iter()
+            .map(|v| v.name.orig())
+            .collect::<Vec<_>>();
+        let variants = self
+            .variants
+            .iter()
+            .map(|v| v.name.to_type_ident())
+            .collect::<Vec<_>>();
+        let error_message = format!("`{{}}` is an invalid value for enum {}", enum_name);
+        let extra_attributes = &self.extra_attributes;
+        let span = current_span();
+        let enum_definition = quote_spanned! {span=>
+            #description
+            #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Serialize, ::lambda_appsync::serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #extra_attributes
+            pub enum #enum_name {
+                #(#variant_descriptions #variant_deprecations #[serde(rename = #variant_orig_iter)]#variants,)*
+            }
+        };
+        let enum_definition = allow_deprecated_variants(
+            &enum_name,
+            enum_definition,
+            self.variants.iter().any(|v| v.deprecation.is_deprecated()),
+        );
+        tokens.extend(quote_spanned! {span=>
+            #enum_definition
+            #[allow(deprecated)]
+            impl #enum_name {
+                pub const COUNT: usize = #count;
+                pub fn all() -> [Self; Self::COUNT] {
+                    [#(Self::#variants,)*]
+                }
+            }
+            #[allow(deprecated)]
+            impl ::core::fmt::Display for #enum_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #(Self::#variants => write!(f, #variant_orig_iter),)*
+                    }
+                }
+            }
+            #[allow(deprecated)]
+            impl ::core::str::FromStr for #enum_name {
+                type Err = ::lambda_appsync::AppsyncError;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    match s {
+                        #(#variant_orig_iter => ::core::result::Result::Ok(Self::#variants),)*
+                        _ => ::core::result::Result::Err(::lambda_appsync::AppsyncError::new(
+                            "InvalidStr",
+                            format!(#error_message, s),
+                        ))
+                    }
+                }
+            }
+        });
+    }
+}
+
+struct Union {
+    name: Name,
+    description: Description,
+    members: Vec<Field>,
+    extra_attributes: ExtraAttributes,
+}
+impl Union {
+    // Unions are serialized with a `__typename` tag, which serde can only add to a map:
+    // a member overridden with a scalar, an enum or a collection would fail at runtime
+    fn apply_type_overrides(
+        &mut self,
+        type_overrides: super::FieldTypeOverrides,
+        non_struct_types: &HashSet<String>,
+    ) -> Result<(), syn::Error> {
+        // In the order of the members, the overrides are not ordered
+        let struct_errors = self
+            .members
+            .iter()
+            .filter_map(|m| {
+                type_overrides
+                    .values()
+                    .filter_map(|(member_override, _)| member_override.as_ref())
+                    .find(|to| to.field_name() == m.name.orig())
+            })
+            .filter(|to| !is_struct_type(to.type_ident_ref(), non_struct_types))
+            .map(|to| {
+                syn::Error::new(
+                    to.type_ident_ref().span(),
+                    format!(
+                        "Member `{}` of union `{}` can only be overridden with a struct, \
+                        unions are serialized with a `__typename` tag",
+                        to.field_name(),
+                        to.type_name()
+                    ),
+                )
+            })
+            .collect::<Vec<_>>();
+        let result = apply_field_type_overrides(&mut self.members, type_overrides, "member");
+        struct_errors
+            .into_iter()
+            .chain(result.err())
+            .reduce(|mut acc, e| {
+                acc.combine(e);
+                acc
+            })
+            .map_or(Ok(()), Err)
+    }
+    fn apply_name_overrides(
+        &mut self,
+        name_overrides: super::TypeNameOverride,
+    ) -> Result<(), syn::Error> {
+        apply_field_name_overrides(&mut self.name, &mut self.members, name_overrides, "member")
+    }
+}
+// Whether `ty` may be a struct: scalars, enums of the schema, collections and
+// non-path types are not
+fn is_struct_type(ty: &syn::Type, non_struct_types: &HashSet<String>) -> bool {
+    const NON_STRUCT_TYPES: &[&str] = &[
+        "String",
+        "str",
+        "bool",
+        "char",
+        "i8",
+        "i16",
+        "i32",
+        "i64",
+        "i128",
+        "isize",
+        "u8",
+        "u16",
+        "u32",
+        "u64",
+        "u128",
+        "usize",
+        "f32",
+        "f64",
+        "Option",
+        "Vec",
+        "VecDeque",
+        "HashSet",
+        "BTreeSet",
+        "Value",
+        "ID",
+        "IpAddr",
+        "AWSEmail",
+        "AWSPhone",
+        "AWSTimestamp",
+        "AWSDate",
+        "AWSTime",
+        "AWSDateTime",
+        "AWSUrl",
+        "Maybe",
+    ];
+    let syn::Type::Path(type_path) = ty else {
+        return false;
+    };
+    match type_path.path.segments.last() {
+        Some(segment) => {
+            let ident = segment.ident.unraw().to_string();
+            type_path.qself.is_none()
+                && !NON_STRUCT_TYPES.contains(&ident.as_str())
+                && !non_struct_types.contains(&ident)
+        }
+        None => false,
+    }
+}
+impl From<graphql_parser::schema::UnionType<'_, String>> for Union {
+    fn from(value: graphql_parser::schema::UnionType<'_, String>) -> Self {
+        let name = Name::from((value.name, current_span()));
+        let description = Description::from(value.description);
+        let members = value
+            .types
+            .into_iter()
+            .map(|member| Field {
+                name: Name::from((member.clone(), current_span())),
+                graphql_type: GraphQLType::Named(member.clone()),
+                field_type: FieldType::from_string(member),
+                description: Description::default(),
+                deprecation: Deprecation::default(),
+                default_value: DefaultValue::default(),
+            })
+            .collect();
+        Self {
+            name,
+            description,
+            members,
+            extra_attributes: ExtraAttributes::default(),
+        }
     }
 }
-impl ToTokens for Structure {
+impl ToTokens for Union {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let span = current_span();
-        let struct_name = self.name.to_type_ident();
-        let fields = self.fields.iter().map(FieldContext::new);
+        let union_name = self.name.to_type_ident();
+        let description = &self.description;
+        let member_orig_iter = self.members.iter().map(|m| m.name.orig());
+        let variants = self
+            .members
+            .iter()
+            .map(|m| m.name.to_type_ident())
+            .collect::<Vec<_>>();
+        let member_types = self
+            .members
+            .iter()
+            .map(|m| &m.field_type)
+            .collect::<Vec<_>>();
+        // `From` conversions are only possible for member types that are not shared
+        // between variants (which can happen with type overrides)
+        let member_type_strs = member_types
+            .iter()
+            .map(|ty| ty.to_token_stream().to_string())
+            .collect::<Vec<_>>();
+        let from_impls = variants
+            .iter()
+            .zip(member_types.iter())
+            .zip(member_type_strs.iter())
+            .filter(|(_, ty_str)| member_type_strs.iter().filter(|s| s == ty_str).count() == 1)
+            .map(|((variant, member_type), _)| {
+                quote_spanned! {span=>
+                    impl ::core::convert::From<#member_type> for #union_name {
+                        fn from(value: #member_type) -> Self {
+                            Self::#variant(value)
+                        }
+                    }
+                }
+            });
+        let extra_attributes = &self.extra_attributes;
+        // AppSync needs the `__typename` of the concrete type to resolve a union
         tokens.extend(quote_spanned! {span=>
+            #description
             #[derive(Debug, Clone, ::lambda_appsync::serde::Serialize, ::lambda_appsync::serde::Deserialize)]
-            pub struct #struct_name {
-                #(#fields,)*
+            #extra_attributes
+            #[serde(tag = "__typename")]
+            pub enum #union_name {
+                #(#[serde(rename = #member_orig_iter)]#variants(#member_types),)*
             }
+            #(#from_impls)*
         });
     }
 }
 
-#[derive(Debug)]
-struct Enum {
+// A Rust struct implementing a GraphQL interface
+struct Implementor {
+    // Original GraphQL type name, used as `__typename`
+    type_name: String,
+    type_ident: proc_macro2::Ident,
+    // Struct field identifiers, in the order of the interface fields
+    field_idents: Vec<proc_macro2::Ident>,
+    // Whether each field is non-null in the struct while nullable in the interface
+    non_null_fields: Vec<bool>,
+}
+
+struct Interface {
     name: Name,
-    variants: Vec<Name>,
+    description: Description,
+    fields: Vec<Field>,
+    implementors: Vec<Implementor>,
+    extra_attributes: ExtraAttributes,
 }
-impl Enum {
-    fn apply_name_overrides(
-        &mut self,
-        (type_override, mut field_overrides): super::TypeNameOverride,
-    ) -> Result<(), syn::Error> {
+impl Interface {
+    fn trait_ident(&self) -> proc_macro2::Ident {
+        format_ident!("{}Fields", self.name.to_type_ident())
+    }
+    fn resolve_implementors(&mut self, structures: &[Structure]) -> Result<(), syn::Error> {
         let mut errors = vec![];
-        if let Some(type_override) = type_override {
-            self.name.override_name(type_override.new_name());
-        }
-        for variant in self.variants.iter_mut() {
-            let variant_name = variant.orig();
-            if let Some(field_override) = field_overrides.remove(variant_name) {
-                variant.override_name(field_override.new_name());
+        for structure in structures
+            .iter()
+            .filter(|s| s.implements.iter().any(|i| i == self.name.orig()))
+        {
+            let mut field_idents = vec![];
+            let mut non_null_fields = vec![];
+            for field in self.fields.iter() {
+                if let Some(struct_field) = structure
+                    .fields
+                    .iter()
+                    .find(|f| f.name.orig() == field.name.orig())
+                {
+                    // The accessors borrow the struct field, so it must have the type of the
+                    // interface field or its non-null version
+                    let non_null =
+                        field.field_type.is_optionnal() && !struct_field.field_type.is_optionnal();
+                    let same_type = |a: &FieldType, b: &FieldType| {
+                        a.to_token_stream().to_string() == b.to_token_stream().to_string()
+                    };
+                    let compatible = if non_null {
+                        same_type(field.field_type.required(), &struct_field.field_type)
+                    } else {
+                        same_type(&field.field_type, &struct_field.field_type)
+                    };
+                    if !compatible {
+                        let struct_graphql_type = struct_field.graphql_type.to_string();
+                        let interface_graphql_type = field.graphql_type.to_string();
+                        let message = if struct_graphql_type == interface_graphql_type
+                            || struct_graphql_type == format!("{interface_graphql_type}!")
+                        {
+                            format!(
+                                "Field `{}` of type `{}` and of interface `{}` have different \
+                                Rust types, use the same `type_override` for both",
+                                field.name.orig(),
+                                structure.name.orig(),
+                                self.name.orig(),
+                            )
+                        } else {
+                            format!(
+                                "Field `{}` of type `{}` is a `{struct_graphql_type}`, which \
+                                cannot be borrowed as the `{interface_graphql_type}` of interface \
+                                `{}`: only the interface field type or its non-null version are \
+                                supported",
+                                field.name.orig(),
+                                structure.name.orig(),
+                                self.name.orig(),
+                            )
+                        };
+                        errors.push(syn::Error::new(current_span(), message));
+                    }
+                    field_idents.push(struct_field.name.to_var_ident());
+                    non_null_fields.push(non_null);
+                } else {
+                    errors.push(syn::Error::new(
+                        current_span(),
+                        format!(
+                            "Type `{}` implements interface `{}` but has no field `{}`",
+                            structure.name.orig(),
+                            self.name.orig(),
+                            field.name.orig()
+                        ),
+                    ));
+                }
             }
-        }
-        if !field_overrides.is_empty() {
-            errors.extend(field_overrides.into_values().map(|no| {
-                syn::Error::new(
-                    no.field_name()
-                        .expect("always set in field_overrides")
-                        .span(),
-                    format!(
-                        "No variant `{}` in `{}`",
-                        no.field_name().expect("always set in field_overrides"),
-                        no.type_name()
-                    ),
-                )
-            }));
+            self.implementors.push(Implementor {
+                type_name: structure.name.orig().to_owned(),
+                type_ident: structure.name.to_type_ident(),
+                field_idents,
+                non_null_fields,
+            });
         }
         if errors.is_empty() {
             Ok(())
@@ -375,73 +2020,175 @@ impl Enum {
                 .expect("not empty"))
         }
     }
+    fn apply_type_overrides(
+        &mut self,
+        type_overrides: super::FieldTypeOverrides,
+    ) -> Result<(), syn::Error> {
+        apply_field_type_overrides(&mut self.fields, type_overrides, "field")
+    }
+    fn apply_name_overrides(
+        &mut self,
+        name_overrides: super::TypeNameOverride,
+    ) -> Result<(), syn::Error> {
+        apply_field_name_overrides(&mut self.name, &mut self.fields, name_overrides, "field")
+    }
 }
-impl From<graphql_parser::schema::EnumType<'_, String>> for Enum {
-    fn from(value: graphql_parser::schema::EnumType<'_, String>) -> Self {
+impl From<graphql_parser::schema::InterfaceType<'_, String>> for Interface {
+    fn from(value: graphql_parser::schema::InterfaceType<'_, String>) -> Self {
         let name = Name::from((value.name, current_span()));
-        let variants = value
-            .values
-            .into_iter()
-            .map(|v| Name::from((v.name, current_span())))
-            .collect();
-        Self { name, variants }
+        let description = Description::from(value.description);
+        let fields = value.fields.into_iter().map(Field::from).collect();
+        Self {
+            name,
+            description,
+            fields,
+            implementors: vec![],
+            extra_attributes: ExtraAttributes::default(),
+        }
     }
 }
-impl ToTokens for Enum {
+impl ToTokens for Interface {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let enum_name = self.name.to_type_ident();
-        let count = proc_macro2::Literal::usize_unsuffixed(self.variants.len());
-        let variant_orig_iter = self.variants.iter().map(|n| n.orig()).collect::<Vec<_>>();
+        let span = current_span();
+        let interface_name = self.name.to_type_ident();
+        let description = &self.description;
+        let trait_name = self.trait_ident();
+        let accessor_descriptions = self.fields.iter().map(|f| &f.description);
+        let accessor_deprecations = self.fields.iter().map(|f| &f.deprecation);
+        let accessors = self
+            .fields
+            .iter()
+            .map(|f| f.name.to_var_ident())
+            .collect::<Vec<_>>();
+        let accessor_types = self
+            .fields
+            .iter()
+            .map(|f| f.field_type.accessor_type())
+            .collect::<Vec<_>>();
+        let type_names = self.implementors.iter().map(|i| i.type_name.as_str());
         let variants = self
-            .variants
+            .implementors
             .iter()
-            .map(|n| n.to_type_ident())
+            .map(|i| &i.type_ident)
             .collect::<Vec<_>>();
-        let error_message = format!("`{{}}` is an invalid value for enum {}", enum_name);
-        let span = current_span();
-        tokens.extend(quote_spanned! {span=>
-            #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Serialize, ::lambda_appsync::serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
-            pub enum #enum_name {
-                #(#[serde(rename = #variant_orig_iter)]#variants,)*
-            }
-            impl #enum_name {
-                pub const COUNT: usize = #count;
-                pub fn all() -> [Self; Self::COUNT] {
-                    [#(Self::#variants,)*]
-                }
-            }
-            impl ::core::fmt::Display for #enum_name {
-                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                    match self {
-                        #(Self::#variants => write!(f, #variant_orig_iter),)*
+        let dispatch_methods =
+            accessors
+                .iter()
+                .zip(accessor_types.iter())
+                .map(|(accessor, accessor_type)| {
+                    quote_spanned! {span=>
+                        fn #accessor(&self) -> #accessor_type {
+                            match *self {
+                                #(Self::#variants(ref v) => #trait_name::#accessor(v),)*
+                            }
+                        }
+                    }
+                });
+        // Implementing the trait on each struct checks the interface fields at compile time
+        let trait_impls = self.implementors.iter().map(|i| {
+            let type_ident = &i.type_ident;
+            let accessor_bodies = self
+                .fields
+                .iter()
+                .zip(i.field_idents.iter().zip(i.non_null_fields.iter()))
+                .map(|(field, (field_ident, non_null))| {
+                    let boxed = field.field_type.is_boxed();
+                    match (field.field_type.is_optionnal(), *non_null, boxed) {
+                        (true, true, true) => {
+                            quote_spanned! {span=>::core::option::Option::Some(&*self.#field_ident)}
+                        }
+                        (true, true, false) => {
+                            quote_spanned! {span=>::core::option::Option::Some(&self.#field_ident)}
+                        }
+                        (true, false, true) => quote_spanned! {span=>self.#field_ident.as_deref()},
+                        (true, false, false) => quote_spanned! {span=>self.#field_ident.as_ref()},
+                        (false, _, _) => quote_spanned! {span=>&self.#field_ident},
                     }
+                });
+            quote_spanned! {span=>
+                #[allow(deprecated)]
+                impl #trait_name for #type_ident {
+                    #(
+                        fn #accessors(&self) -> #accessor_types {
+                            #accessor_bodies
+                        }
+                    )*
                 }
-            }
-            impl ::core::str::FromStr for #enum_name {
-                type Err = ::lambda_appsync::AppsyncError;
-
-                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
-                    match s {
-                        #(#variant_orig_iter => ::core::result::Result::Ok(Self::#variants),)*
-                        _ => ::core::result::Result::Err(::lambda_appsync::AppsyncError::new(
-                            "InvalidStr",
-                            format!(#error_message, s),
-                        ))
+                impl ::core::convert::From<#type_ident> for #interface_name {
+                    fn from(value: #type_ident) -> Self {
+                        Self::#type_ident(value)
                     }
                 }
             }
         });
+        let extra_attributes = &self.extra_attributes;
+        // AppSync needs the `__typename` of the concrete type to resolve an interface
+        tokens.extend(quote_spanned! {span=>
+            #description
+            pub trait #trait_name {
+                #(#accessor_descriptions #accessor_deprecations fn #accessors(&self) -> #accessor_types;)*
+            }
+            #description
+            #[derive(Debug, Clone, ::lambda_appsync::serde::Serialize, ::lambda_appsync::serde::Deserialize)]
+            #extra_attributes
+            #[serde(tag = "__typename")]
+            pub enum #interface_name {
+                #(#[serde(rename = #type_names)]#variants(#variants),)*
+            }
+            #[allow(deprecated)]
+            impl #trait_name for #interface_name {
+                #(#dispatch_methods)*
+            }
+            #(#trait_impls)*
+        });
     }
 }
 
 struct Operation {
     name: Name,
+    description: Description,
+    deprecation: Deprecation,
+    auth: AuthDirectives,
+    subscribed_mutations: SubscribedMutations,
     args: Vec<Field>,
     return_type: FieldType,
+    graphql_type: GraphQLType,
+    // GraphQL return type, compared between subscriptions and the mutations triggering them
+    payload_type: String,
+    position: graphql_parser::Pos,
 }
 impl Operation {
-    fn variant(&self) -> proc_macro2::Ident {
-        self.name.to_type_ident()
+    fn variant(&self) -> proc_macro2::TokenStream {
+        let description = &self.description;
+        let deprecation = &self.deprecation;
+        let variant = self.name.to_type_ident();
+        quote! {
+            #description
+            #deprecation
+            #variant
+        }
+    }
+    // The operation description followed by the description of its arguments
+    fn module_description(&self) -> Description {
+        let mut module_description = self.description.0.clone().unwrap_or_default();
+        let arg_descriptions = self
+            .args
+            .iter()
+            .filter_map(|arg| {
+                arg.description
+                    .0
+                    .as_ref()
+                    .map(|d| format!("- `{}`: {}", arg.name.orig(), d.replace('\n', "\n  ")))
+            })
+            .collect::<Vec<_>>();
+        if !arg_descriptions.is_empty() {
+            if !module_description.is_empty() {
+                module_description.push_str("\n\n");
+            }
+            module_description.push_str("# Arguments\n\n");
+            module_description.push_str(&arg_descriptions.join("\n"));
+        }
+        Description::from(Some(module_description))
     }
     fn default_op(&self, kind: OperationKind) -> proc_macro2::TokenStream {
         let fct_name = self.name.to_prefixed_fct_ident(kind.fct_prefix());
@@ -484,30 +2231,119 @@ impl Operation {
         quote_spanned! {span=>
             #operation_enum_name::#variant => Operation::#fct_name(event)
             .await
-            .map(::lambda_appsync::res_to_json)
+            .map(::lambda_appsync::IntoPartialData::into_partial_data)
+        }
+    }
+    fn output_type_match_arm(
+        &self,
+        field_enum_name: &proc_macro2::Ident,
+        output_types: &OutputTypes,
+    ) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let variant = self.name.to_type_ident();
+        let field_name = self.name.orig();
+        let output_type = output_types.to_tokens(&self.graphql_type);
+        quote_spanned! {span=>
+            #field_enum_name::#variant => {
+                static OUTPUT_TYPE: ::lambda_appsync::OutputType = #output_type;
+                (#field_name, &OUTPUT_TYPE)
+            }
+        }
+    }
+    fn auth_match_arms(
+        &self,
+        field_enum_name: &proc_macro2::Ident,
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let span = current_span();
+        let variant = self.name.to_type_ident();
+        let modes = self.auth.modes_tokens();
+        let cognito_groups = self.auth.cognito_groups_tokens();
+        (
+            quote_spanned! {span=>#field_enum_name::#variant => #modes},
+            quote_spanned! {span=>#field_enum_name::#variant => #cognito_groups},
+        )
+    }
+    fn default_field_op(&self, parent: &Name) -> proc_macro2::TokenStream {
+        let fct_name = self.name.to_field_fct_ident(parent);
+        let span = current_span();
+        let return_type = &self.return_type;
+        let unimplemented_message = format!(
+            "Field `{}.{}` is unimplemented",
+            parent.orig(),
+            self.name.orig()
+        );
+        quote_spanned! {span=>
+            async fn #fct_name(_event: ::lambda_appsync::AppsyncEvent<Operation>) -> ::core::result::Result<#return_type, ::lambda_appsync::AppsyncError> {
+                ::core::result::Result::Err(::lambda_appsync::AppsyncError::new(
+                    "Unimplemented",
+                    #unimplemented_message,
+                ))
+            }
+        }
+    }
+    fn execute_field_match_arm(
+        &self,
+        parent: &Name,
+        field_enum_name: &proc_macro2::Ident,
+    ) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let variant = self.name.to_type_ident();
+        let fct_name = self.name.to_field_fct_ident(parent);
+        quote_spanned! {span=>
+            #field_enum_name::#variant => Operation::#fct_name(event)
+            .await
+            .map(::lambda_appsync::IntoPartialData::into_partial_data)
         }
     }
-    fn argument_extractor(&self, with_event: bool) -> proc_macro2::TokenStream {
+    fn argument_extractor(
+        &self,
+        with_event: bool,
+        parent_type: Option<&proc_macro2::Ident>,
+    ) -> proc_macro2::TokenStream {
         let span = current_span();
         let params_types = self.args.iter().map(|arg| &arg.field_type);
-        let param_strs = self.args.iter().map(|arg| arg.name.orig());
+        let params_values = self
+            .args
+            .iter()
+            .map(|arg| {
+                let param_str = arg.name.orig();
+                if arg.default_value.is_set() {
+                    let default_value = &arg.default_value;
+                    quote! {::lambda_appsync::arg_from_json_or(&mut args, #param_str, #default_value)?}
+                } else if arg.field_type.is_maybe() {
+                    quote! {::lambda_appsync::arg_from_json_maybe(&mut args, #param_str)?}
+                } else {
+                    quote! {::lambda_appsync::arg_from_json(&mut args, #param_str)?}
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Field resolvers receive their parent, deserialized from the event source, first
+        let (parent_param, parent_value) = if let Some(parent_type) = parent_type {
+            (
+                quote! {#parent_type,},
+                quote! {::lambda_appsync::source_from_json(&mut event.source)?,},
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
 
         let return_type = if with_event {
             quote! {
-                (#(#params_types,)* &::lambda_appsync::AppsyncEvent<Operation>,)
+                (#parent_param #(#params_types,)* &::lambda_appsync::AppsyncEvent<Operation>,)
             }
         } else {
             quote! {
-                (#(#params_types,)*)
+                (#parent_param #(#params_types,)*)
             }
         };
         let returned_tuple = if with_event {
             quote! {
-                (#(::lambda_appsync::arg_from_json(&mut args, #param_strs)?,)* event,)
+                (#parent_value #(#params_values,)* event,)
             }
         } else {
             quote! {
-                (#(::lambda_appsync::arg_from_json(&mut args, #param_strs)?,)*)
+                (#parent_value #(#params_values,)*)
             }
         };
 
@@ -520,44 +2356,106 @@ impl Operation {
                 let mut args = event.args.take();
             }
         };
+        // The tuple of many arguments can trip the type complexity lint in user crates
         quote_spanned! {span=>
+            #[allow(clippy::type_complexity)]
             pub(crate) fn operation_arguments(event: &mut ::lambda_appsync::AppsyncEvent<Operation>) -> ::core::result::Result<#return_type, ::lambda_appsync::AppsyncError> {
                 #extract_args
                  Ok(#returned_tuple)
             }
         }
     }
-    fn operation_module(&self, kind: OperationKind) -> proc_macro2::TokenStream {
-        let module_name = self.name.to_var_ident();
-        let params_types = self
-            .args
-            .iter()
-            .map(|arg| &arg.field_type)
-            .collect::<Vec<_>>();
-        let arument_extractor_without_event = self.argument_extractor(false);
-        let arument_extractor_with_event = self.argument_extractor(true);
-        let return_type = match kind {
+    fn operation_module(
+        &self,
+        kind: OperationKind,
+        warn_deprecated: bool,
+    ) -> proc_macro2::TokenStream {
+        let deprecation_note = self
+            .deprecation
+            .reason()
+            .filter(|_| warn_deprecated)
+            .map(|reason| format!("{kind} `{}` is deprecated: {reason}", self.name.orig()));
+        let (data_type, data_span) = match kind {
             OperationKind::Query | OperationKind::Mutation => {
-                let return_type = &self.return_type;
-                quote_spanned! {return_type.span()=>
-                    ::core::result::Result<#return_type, ::lambda_appsync::AppsyncError>
-                }
+                (self.return_type.to_token_stream(), self.return_type.span())
             }
-            OperationKind::Subscription => quote_spanned! {current_span()=>
-                ::core::result::Result<::core::option::Option<::lambda_appsync::subscription_filters::FilterGroup>, ::lambda_appsync::AppsyncError>
-            },
+            OperationKind::Subscription => (
+                quote_spanned! {current_span()=>
+                    ::core::option::Option<::lambda_appsync::subscription_filters::FilterGroup>
+                },
+                current_span(),
+            ),
+        };
+        self.signature_module(data_type, data_span, None, deprecation_note)
+    }
+    fn field_module(
+        &self,
+        parent: &Name,
+        parent_type: &proc_macro2::Ident,
+        warn_deprecated: bool,
+    ) -> proc_macro2::TokenStream {
+        let deprecation_note =
+            self.deprecation
+                .reason()
+                .filter(|_| warn_deprecated)
+                .map(|reason| {
+                    format!(
+                        "Field `{}.{}` is deprecated: {reason}",
+                        parent.orig(),
+                        self.name.orig()
+                    )
+                });
+        self.signature_module(
+            self.return_type.to_token_stream(),
+            self.return_type.span(),
+            Some(parent_type),
+            deprecation_note,
+        )
+    }
+    fn signature_module(
+        &self,
+        data_type: proc_macro2::TokenStream,
+        data_span: proc_macro2::Span,
+        parent_type: Option<&proc_macro2::Ident>,
+        deprecation_note: Option<String>,
+    ) -> proc_macro2::TokenStream {
+        // Handlers return their data, optionally with partial data, or any error convertible
+        // into an `AppsyncError`
+        let return_type = quote_spanned! {data_span=>
+            R: ::lambda_appsync::OperationResult<#data_type>
+        };
+        let module_name = self.name.to_var_ident();
+        let module_description = self.module_description();
+        let params_types = parent_type
+            .into_iter()
+            .map(ToTokens::to_token_stream)
+            .chain(self.args.iter().map(|arg| arg.field_type.to_token_stream()))
+            .collect::<Vec<_>>();
+        let arument_extractor_without_event = self.argument_extractor(false, parent_type);
+        let arument_extractor_with_event = self.argument_extractor(true, parent_type);
+        // Field modules are nested one level deeper, under their parent type module
+        let operations_module = if parent_type.is_some() {
+            quote! {super::super::super::super}
+        } else {
+            quote! {super::super::super}
         };
+        // The signature check is the only item used by `appsync_operation`, deprecating it
+        // makes implementing a deprecated operation a compile warning
+        let deprecation = deprecation_note.map(|note| quote! {#[deprecated(note = #note)]});
 
         quote! {
+            #module_description
             pub(crate) mod #module_name {
                 pub(crate) mod without_event {
-                    use super::super::super::*;
-                    pub(crate) fn check_signature<F: Fn(#(#params_types),*) -> #return_type>(_f: F) {}
+                    use #operations_module::*;
+                    #deprecation
+                    pub(crate) fn check_signature<#return_type, F: Fn(#(#params_types),*) -> R>(_f: F) {}
                     #arument_extractor_without_event
                 }
                 pub(crate) mod with_event {
-                    use super::super::super::*;
-                    pub(crate) fn check_signature<F: Fn(#(#params_types,)* &::lambda_appsync::AppsyncEvent<Operation>) -> #return_type>(_f: F) {}
+                    use #operations_module::*;
+                    #deprecation
+                    pub(crate) fn check_signature<#return_type, F: Fn(#(#params_types,)* &::lambda_appsync::AppsyncEvent<Operation>) -> R>(_f: F) {}
                     #arument_extractor_with_event
                 }
             }
@@ -608,12 +2506,25 @@ impl Operation {
 impl From<graphql_parser::schema::Field<'_, String>> for Operation {
     fn from(value: graphql_parser::schema::Field<'_, String>) -> Self {
         let name = Name::from(value.name);
+        let description = Description::from(value.description);
+        let deprecation = Deprecation::from(value.directives.as_slice());
+        let auth = AuthDirectives::from(value.directives.as_slice());
+        let subscribed_mutations = SubscribedMutations::from(value.directives.as_slice());
         let args = value.arguments.into_iter().map(Field::from).collect();
+        let payload_type = payload_type(&value.field_type);
+        let graphql_type = GraphQLType::from(&value.field_type);
         let return_type = FieldType::from(value.field_type);
         Self {
             name,
+            description,
+            deprecation,
+            auth,
+            subscribed_mutations,
             args,
             return_type,
+            graphql_type,
+            payload_type,
+            position: value.position,
         }
     }
 }
@@ -622,13 +2533,40 @@ impl From<graphql_parser::schema::Field<'_, String>> for Operation {
 struct Operations(Vec<Operation>);
 impl From<graphql_parser::schema::ObjectType<'_, String>> for Operations {
     fn from(value: graphql_parser::schema::ObjectType<'_, String>) -> Self {
-        Self(value.fields.into_iter().map(Operation::from).collect())
+        let type_auth = AuthDirectives::from(value.directives.as_slice());
+        Self(
+            value
+                .fields
+                .into_iter()
+                .map(Operation::from)
+                .map(|mut op| {
+                    op.auth = op.auth.or(&type_auth);
+                    op
+                })
+                .collect(),
+        )
     }
 }
 impl Operations {
-    fn variants_iter(&self) -> impl Iterator<Item = proc_macro2::Ident> + '_ {
+    fn variants_iter(&self) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
         self.0.iter().map(Operation::variant)
     }
+    fn field_enum(&self, field_enum_name: &proc_macro2::Ident) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let variants = self.variants_iter();
+        let field_enum = quote_spanned! {span=>
+            #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Deserialize)]
+            #[serde(rename_all = "camelCase")]
+            pub enum #field_enum_name {
+                #(#variants,)*
+            }
+        };
+        allow_deprecated_variants(
+            field_enum_name,
+            field_enum,
+            self.0.iter().any(|op| op.deprecation.is_deprecated()),
+        )
+    }
     fn default_op_iter(
         &self,
         kind: OperationKind,
@@ -641,11 +2579,87 @@ impl Operations {
     ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
         self.0.iter().map(move |op| op.execute_match_arm(kind))
     }
+    // The match arms of the `check_response` method
+    fn output_type_match_arms(
+        &self,
+        field_enum_name: &proc_macro2::Ident,
+        output_types: &OutputTypes,
+    ) -> Vec<proc_macro2::TokenStream> {
+        self.0
+            .iter()
+            .map(|op| op.output_type_match_arm(field_enum_name, output_types))
+            .collect()
+    }
+    // The match arms of the `allowed_auth_modes` and `required_cognito_groups` methods
+    fn auth_match_arms(
+        &self,
+        field_enum_name: &proc_macro2::Ident,
+    ) -> (Vec<proc_macro2::TokenStream>, Vec<proc_macro2::TokenStream>) {
+        self.0
+            .iter()
+            .map(|op| op.auth_match_arms(field_enum_name))
+            .unzip()
+    }
     fn operation_module_iter(
         &self,
         kind: OperationKind,
+        warn_deprecated: bool,
     ) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
-        self.0.iter().map(move |op| op.operation_module(kind))
+        self.0
+            .iter()
+            .map(move |op| op.operation_module(kind, warn_deprecated))
+    }
+    // Builds the operations of the fields of `object_type` that are resolved by the Lambda
+    fn field_resolvers(
+        object_type: &graphql_parser::schema::ObjectType<'_, String>,
+        mut field_resolvers: super::FieldResolverNames,
+        type_overrides: Option<&mut super::FieldTypeOverrides>,
+    ) -> Result<Self, syn::Error> {
+        let mut errors = vec![];
+        let mut fields = vec![];
+        let type_auth = AuthDirectives::from(object_type.directives.as_slice());
+        for field in object_type.fields.iter() {
+            if field_resolvers.remove(&field.name).is_some() {
+                let mut op = Operation::from(field.clone());
+                op.auth = op.auth.or(&type_auth);
+                fields.push(op);
+            }
+        }
+        errors.extend(field_resolvers.into_values().map(|fr| {
+            syn::Error::new(
+                fr.field_name().span(),
+                format!("No field `{}` in `{}`", fr.field_name(), fr.type_name()),
+            )
+        }));
+        // The field type override also applies to the structure, only the arg overrides are consumed
+        if let Some(type_overrides) = type_overrides {
+            for op in fields.iter_mut() {
+                if let Some((field_override, args_override)) = type_overrides.remove(op.name.orig())
+                {
+                    match op.apply_type_overrides((field_override.clone(), args_override)) {
+                        Ok(_) => (),
+                        Err(e) => errors.push(e),
+                    };
+                    if field_override.is_some() {
+                        type_overrides.insert(
+                            op.name.orig().to_owned(),
+                            (field_override, Default::default()),
+                        );
+                    }
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(Self(fields))
+        } else {
+            Err(errors
+                .into_iter()
+                .reduce(|mut acc, e| {
+                    acc.combine(e);
+                    acc
+                })
+                .expect("not empty"))
+        }
     }
     fn apply_type_overrides(
         &mut self,
@@ -688,6 +2702,98 @@ impl Operations {
     }
 }
 
+// A non-root type with some of its fields resolved by the Lambda
+struct ResolverParent {
+    // Original GraphQL name, as received in `parentTypeName`, never overridden
+    name: Name,
+    // Rust type of the parent, deserialized from the event `source`
+    type_ident: proc_macro2::Ident,
+    fields: Operations,
+}
+impl ResolverParent {
+    fn field_enum_name(&self) -> proc_macro2::Ident {
+        format_ident!("{}Field", self.type_ident)
+    }
+    fn field_enum(&self) -> proc_macro2::TokenStream {
+        self.fields.field_enum(&self.field_enum_name())
+    }
+    fn operation_variant(&self) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let orig_name = self.name.orig();
+        let variant = &self.type_ident;
+        let field_enum_name = self.field_enum_name();
+        quote_spanned! {span=>
+            #[serde(rename = #orig_name)]
+            #variant(#field_enum_name)
+        }
+    }
+    fn default_op_iter(&self) -> impl Iterator<Item = proc_macro2::TokenStream> + '_ {
+        self.fields
+            .0
+            .iter()
+            .map(|op| op.default_field_op(&self.name))
+    }
+    fn execute_match_arm(&self) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let variant = &self.type_ident;
+        let field_enum_name = self.field_enum_name();
+        let field_match_arms = self
+            .fields
+            .0
+            .iter()
+            .map(|op| op.execute_field_match_arm(&self.name, &field_enum_name));
+        quote_spanned! {span=>
+            Operation::#variant(field) => match field {
+                #(#field_match_arms,)*
+            }
+        }
+    }
+    fn output_type_match_arm(&self, output_types: &OutputTypes) -> proc_macro2::TokenStream {
+        let span = current_span();
+        let variant = &self.type_ident;
+        let field_enum_name = self.field_enum_name();
+        let field_match_arms = self
+            .fields
+            .output_type_match_arms(&field_enum_name, output_types);
+        quote_spanned! {span=>
+            Operation::#variant(field) => match field {
+                #(#field_match_arms,)*
+            }
+        }
+    }
+    fn auth_match_arms(&self) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let span = current_span();
+        let variant = &self.type_ident;
+        let (modes_match_arms, cognito_groups_match_arms) =
+            self.fields.auth_match_arms(&self.field_enum_name());
+        (
+            quote_spanned! {span=>
+                Operation::#variant(field) => match field {
+                    #(#modes_match_arms,)*
+                }
+            },
+            quote_spanned! {span=>
+                Operation::#variant(field) => match field {
+                    #(#cognito_groups_match_arms,)*
+                }
+            },
+        )
+    }
+    fn operation_module(&self, warn_deprecated: bool) -> proc_macro2::TokenStream {
+        let module_name = self.name.to_var_ident();
+        let field_modules = self
+            .fields
+            .0
+            .iter()
+            .map(|op| op.field_module(&self.name, &self.type_ident, warn_deprecated));
+        quote! {
+            pub(crate) mod #module_name {
+                #(#field_modules)*
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 struct SchemaDefinition {
     query: String,
@@ -732,25 +2838,279 @@ impl From<graphql_parser::schema::SchemaDefinition<'_, String>> for SchemaDefini
     }
 }
 
+fn type_definition_name<'a>(td: &'a TypeDefinition<'_, String>) -> &'a str {
+    match td {
+        TypeDefinition::Scalar(t) => &t.name,
+        TypeDefinition::Object(t) => &t.name,
+        TypeDefinition::Interface(t) => &t.name,
+        TypeDefinition::Union(t) => &t.name,
+        TypeDefinition::Enum(t) => &t.name,
+        TypeDefinition::InputObject(t) => &t.name,
+    }
+}
+
+// A type containing itself by value, directly or through other types, has an infinite size:
+// every field that is part of such a cycle is boxed
+fn box_recursive_types(
+    structures: &mut [Structure],
+    unions: &[Union],
+    interfaces: &mut [Interface],
+) {
+    // Types contained by value in each type, unions and interfaces contain their members
+    let mut contained_types: HashMap<String, Vec<String>> = HashMap::new();
+    for structure in structures.iter() {
+        contained_types.insert(
+            structure.name.orig().to_owned(),
+            structure
+                .fields
+                .iter()
+                .filter_map(|f| f.field_type.contained_type())
+                .map(str::to_owned)
+                .collect(),
+        );
+    }
+    for r_union in unions.iter() {
+        contained_types.insert(
+            r_union.name.orig().to_owned(),
+            r_union
+                .members
+                .iter()
+                .filter_map(|m| m.field_type.contained_type())
+                .map(str::to_owned)
+                .collect(),
+        );
+    }
+    for interface in interfaces.iter() {
+        contained_types.insert(
+            interface.name.orig().to_owned(),
+            interface
+                .implementors
+                .iter()
+                .map(|i| i.type_name.clone())
+                .collect(),
+        );
+    }
+    let contains = |from: &str, to: &str| {
+        let mut visited = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(type_name) = stack.pop() {
+            if type_name == to {
+                return true;
+            }
+            if visited.insert(type_name) {
+                stack.extend(
+                    contained_types
+                        .get(type_name)
+                        .into_iter()
+                        .flatten()
+                        .map(String::as_str),
+                );
+            }
+        }
+        false
+    };
+    for structure in structures.iter_mut() {
+        let struct_name = structure.name.orig().to_owned();
+        for field in structure.fields.iter_mut() {
+            if field
+                .field_type
+                .contained_type()
+                .is_some_and(|contained| contains(contained, &struct_name))
+            {
+                field.field_type.set_boxed();
+            }
+        }
+    }
+
+    // The interface traits return references to the fields of their implementors,
+    // so a field boxed in one of them is boxed in the interface and all the others
+    for interface in interfaces.iter_mut() {
+        let interface_name = interface.name.orig().to_owned();
+        for field in interface.fields.iter_mut() {
+            let mut implementor_fields = structures
+                .iter_mut()
+                .filter(|s| s.implements.contains(&interface_name))
+                .filter_map(|s| {
+                    s.fields
+                        .iter_mut()
+                        .find(|f| f.name.orig() == field.name.orig())
+                })
+                .collect::<Vec<_>>();
+            if implementor_fields.iter().any(|f| f.field_type.is_boxed()) {
+                field.field_type.set_boxed();
+                for implementor_field in implementor_fields.iter_mut() {
+                    implementor_field.field_type.set_boxed();
+                }
+            }
+        }
+    }
+}
+
+// Merges every `extend ...` definition into its base definition, so the rest of
+// the code generation never has to care about extensions
+fn merge_type_extensions<'a>(
+    definitions: Vec<Definition<'a, String>>,
+    schema_files: &super::SchemaFiles,
+) -> Result<Vec<Definition<'a, String>>, syn::Error> {
+    let (extensions, mut definitions): (Vec<_>, Vec<_>) = definitions
+        .into_iter()
+        .partition(|def| matches!(def, Definition::TypeExtension(_)));
+
+    let mut errors = vec![];
+    for extension in extensions {
+        let Definition::TypeExtension(extension) = extension else {
+            unreachable!("just partitioned on type extensions")
+        };
+        let (kind, name, position) = match &extension {
+            TypeExtension::Scalar(e) => ("scalar", e.name.clone(), e.position),
+            TypeExtension::Object(e) => ("type", e.name.clone(), e.position),
+            TypeExtension::Interface(e) => ("interface", e.name.clone(), e.position),
+            TypeExtension::Union(e) => ("union", e.name.clone(), e.position),
+            TypeExtension::Enum(e) => ("enum", e.name.clone(), e.position),
+            TypeExtension::InputObject(e) => ("input", e.name.clone(), e.position),
+        };
+        let base = definitions.iter_mut().find_map(|def| match def {
+            Definition::TypeDefinition(td) if type_definition_name(td) == name => Some(td),
+            _ => None,
+        });
+        // An extension can only add new fields, values or members
+        let duplicate_errors = |item: &str, existing: Vec<&String>, added: Vec<&String>| {
+            added
+                .iter()
+                .enumerate()
+                .filter(|(i, item_name)| {
+                    existing.contains(item_name) || added[..*i].contains(item_name)
+                })
+                .map(|(_, item_name)| {
+                    let (span, location) = schema_files.locate(position);
+                    syn::Error::new(
+                        span,
+                        format!(
+                            "`extend {kind} {name}` ({location}): `{name}` already has \
+                            {item} `{item_name}`"
+                        ),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        match (base, extension) {
+            (Some(TypeDefinition::Scalar(base)), TypeExtension::Scalar(e)) => {
+                base.directives.extend(e.directives);
+            }
+            (Some(TypeDefinition::Object(base)), TypeExtension::Object(e)) => {
+                errors.extend(duplicate_errors(
+                    "a field",
+                    base.fields.iter().map(|f| &f.name).collect(),
+                    e.fields.iter().map(|f| &f.name).collect(),
+                ));
+                base.implements_interfaces.extend(e.implements_interfaces);
+                base.directives.extend(e.directives);
+                base.fields.extend(e.fields);
+            }
+            (Some(TypeDefinition::Interface(base)), TypeExtension::Interface(e)) => {
+                errors.extend(duplicate_errors(
+                    "a field",
+                    base.fields.iter().map(|f| &f.name).collect(),
+                    e.fields.iter().map(|f| &f.name).collect(),
+                ));
+                base.implements_interfaces.extend(e.implements_interfaces);
+                base.directives.extend(e.directives);
+                base.fields.extend(e.fields);
+            }
+            (Some(TypeDefinition::Union(base)), TypeExtension::Union(e)) => {
+                errors.extend(duplicate_errors(
+                    "a member",
+                    base.types.iter().collect(),
+                    e.types.iter().collect(),
+                ));
+                base.directives.extend(e.directives);
+                base.types.extend(e.types);
+            }
+            (Some(TypeDefinition::Enum(base)), TypeExtension::Enum(e)) => {
+                errors.extend(duplicate_errors(
+                    "a value",
+                    base.values.iter().map(|v| &v.name).collect(),
+                    e.values.iter().map(|v| &v.name).collect(),
+                ));
+                base.directives.extend(e.directives);
+                base.values.extend(e.values);
+            }
+            (Some(TypeDefinition::InputObject(base)), TypeExtension::InputObject(e)) => {
+                errors.extend(duplicate_errors(
+                    "a field",
+                    base.fields.iter().map(|f| &f.name).collect(),
+                    e.fields.iter().map(|f| &f.name).collect(),
+                ));
+                base.directives.extend(e.directives);
+                base.fields.extend(e.fields);
+            }
+            (Some(_), _) => {
+                let article = if kind.starts_with(['a', 'e', 'i', 'o', 'u']) {
+                    "an"
+                } else {
+                    "a"
+                };
+                let (span, location) = schema_files.locate(position);
+                errors.push(syn::Error::new(
+                    span,
+                    format!(
+                        "`extend {kind} {name}` ({location}): `{name}` is not {article} {kind}"
+                    ),
+                ))
+            }
+            (None, _) => {
+                let (span, location) = schema_files.locate(position);
+                errors.push(syn::Error::new(
+                    span,
+                    format!("`extend {kind} {name}` ({location}): no {kind} named `{name}`"),
+                ))
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(definitions)
+    } else {
+        Err(errors
+            .into_iter()
+            .reduce(|mut acc, e| {
+                acc.combine(e);
+                acc
+            })
+            .expect("not empty"))
+    }
+}
+
 pub(crate) struct GraphQLSchema {
     queries: Operations,
     mutations: Operations,
     subscriptions: Operations,
     structures: Vec<Structure>,
     enums: Vec<Enum>,
+    unions: Vec<Union>,
+    interfaces: Vec<Interface>,
+    resolver_parents: Vec<ResolverParent>,
+    // Check the results of the operations against the schema with the `check_responses` option
+    check_responses: bool,
 }
 impl GraphQLSchema {
     pub(crate) fn new(
         mut doc: Document<'_, String>,
         span: proc_macro2::Span,
+        schema_files: &super::SchemaFiles,
         mut tos: super::TypeOverrides,
         mut nos: super::NameOverrides,
+        sms: super::ScalarMaps,
+        mut frs: super::FieldResolvers,
     ) -> Result<Self, syn::Error> {
         let mut queries = None;
         let mut mutations = None;
         let mut subscriptions = None;
         let mut structures = vec![];
         let mut enums = vec![];
+        let mut unions = vec![];
+        let mut interfaces = vec![];
+        let mut resolver_parents = vec![];
+        let mut custom_scalars = vec![];
 
         CURRENT_SPAN.replace(span);
 
@@ -767,8 +3127,20 @@ impl GraphQLSchema {
             SchemaDefinition::default()
         };
 
+        let definitions = merge_type_extensions(doc.definitions, schema_files)?;
+        // Enums and scalars of the schema, which cannot be union members
+        let non_struct_types = definitions
+            .iter()
+            .filter_map(|def| match def {
+                Definition::TypeDefinition(
+                    td @ (TypeDefinition::Enum(_) | TypeDefinition::Scalar(_)),
+                ) => Some(type_definition_name(td).to_owned()),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+
         let mut errors = vec![];
-        for def in doc.definitions {
+        for def in definitions {
             match def {
                 Definition::TypeDefinition(type_definition) => {
                     match type_definition {
@@ -794,8 +3166,19 @@ impl GraphQLSchema {
                                     }
                                 }
                             } else {
+                                let mut type_overrides = tos.remove(&object_type.name);
+                                let resolver_fields =
+                                    frs.remove(&object_type.name).and_then(|field_resolvers| {
+                                        Operations::field_resolvers(
+                                            &object_type,
+                                            field_resolvers,
+                                            type_overrides.as_mut(),
+                                        )
+                                        .map_err(|e| errors.push(e))
+                                        .ok()
+                                    });
                                 let mut structure = Structure::from(object_type);
-                                if let Some(type_overrides) = tos.remove(structure.name.orig()) {
+                                if let Some(type_overrides) = type_overrides {
                                     match structure.apply_type_overrides(type_overrides) {
                                         Ok(_) => (),
                                         Err(e) => errors.push(e),
@@ -807,6 +3190,17 @@ impl GraphQLSchema {
                                         Err(e) => errors.push(e),
                                     };
                                 }
+                                if let Some(fields) = resolver_fields {
+                                    structure.set_resolved_fields_nullable(&fields);
+                                    resolver_parents.push(ResolverParent {
+                                        name: Name::from((
+                                            structure.name.orig().to_owned(),
+                                            current_span(),
+                                        )),
+                                        type_ident: structure.name.to_type_ident(),
+                                        fields,
+                                    });
+                                }
                                 structures.push(structure);
                             }
                         }
@@ -836,10 +3230,46 @@ impl GraphQLSchema {
                             }
                             structures.push(structure);
                         }
-                        // Not yet implemented, ignored for now
-                        TypeDefinition::Scalar(_) => {}
-                        TypeDefinition::Interface(_) => (),
-                        TypeDefinition::Union(_) => (),
+                        TypeDefinition::Union(union_type) => {
+                            let mut r_union = Union::from(union_type);
+                            if let Some(type_overrides) = tos.remove(r_union.name.orig()) {
+                                match r_union
+                                    .apply_type_overrides(type_overrides, &non_struct_types)
+                                {
+                                    Ok(_) => (),
+                                    Err(e) => errors.push(e),
+                                };
+                            }
+                            if let Some(name_overrides) = nos.remove(r_union.name.orig()) {
+                                match r_union.apply_name_overrides(name_overrides) {
+                                    Ok(_) => (),
+                                    Err(e) => errors.push(e),
+                                };
+                            }
+                            unions.push(r_union);
+                        }
+                        TypeDefinition::Interface(interface_type) => {
+                            let mut interface = Interface::from(interface_type);
+                            if let Some(type_overrides) = tos.remove(interface.name.orig()) {
+                                match interface.apply_type_overrides(type_overrides) {
+                                    Ok(_) => (),
+                                    Err(e) => errors.push(e),
+                                };
+                            }
+                            if let Some(name_overrides) = nos.remove(interface.name.orig()) {
+                                match interface.apply_name_overrides(name_overrides) {
+                                    Ok(_) => (),
+                                    Err(e) => errors.push(e),
+                                };
+                            }
+                            interfaces.push(interface);
+                        }
+                        TypeDefinition::Scalar(scalar_type) => {
+                            // AWS scalars are built-in, some tools still declare them
+                            if Scalar::try_from(scalar_type.name.as_str()).is_err() {
+                                custom_scalars.push(scalar_type.name);
+                            }
+                        }
                     }
                 }
                 // Already processed
@@ -849,12 +3279,236 @@ impl GraphQLSchema {
                         "GraphQL schema file has two `schema` definition",
                     ));
                 }
+                Definition::TypeExtension(_) => {
+                    unreachable!("type extensions are merged into their base definition")
+                }
                 // Ignored for now
-                Definition::TypeExtension(_) => (),
                 Definition::DirectiveDefinition(_) => (),
             }
         }
 
+        // Custom scalars can be used before being declared, so they are mapped last
+        for scalar_name in custom_scalars.iter() {
+            if !sms.contains_key(scalar_name) {
+                errors.push(syn::Error::new(
+                    span,
+                    format!(
+                        "Custom scalar `{scalar_name}` has no Rust type, \
+                        use `scalar_map = {scalar_name}: path::to::Type` to provide one"
+                    ),
+                ));
+            }
+        }
+        errors.extend(
+            sms.values()
+                .filter(|sm| !custom_scalars.contains(&sm.scalar_name().to_string()))
+                .map(|sm| {
+                    syn::Error::new(
+                        sm.scalar_name().span(),
+                        format!("No custom scalar named `{}`", sm.scalar_name()),
+                    )
+                }),
+        );
+        for structure in structures.iter_mut() {
+            for field in structure.fields.iter_mut() {
+                field.field_type.apply_scalar_maps(&sms);
+            }
+        }
+        for r_union in unions.iter_mut() {
+            for member in r_union.members.iter_mut() {
+                member.field_type.apply_scalar_maps(&sms);
+            }
+        }
+        for interface in interfaces.iter_mut() {
+            for field in interface.fields.iter_mut() {
+                field.field_type.apply_scalar_maps(&sms);
+            }
+        }
+        for ops in [&mut queries, &mut mutations, &mut subscriptions]
+            .into_iter()
+            .flatten()
+            .chain(resolver_parents.iter_mut().map(|rp| &mut rp.fields))
+        {
+            for op in ops.0.iter_mut() {
+                op.return_type.apply_scalar_maps(&sms);
+                for arg in op.args.iter_mut() {
+                    arg.field_type.apply_scalar_maps(&sms);
+                }
+            }
+        }
+
+        // The selection set of an event applies to the type returned by its operation
+        let operation_results = [&queries, &mutations]
+            .into_iter()
+            .flatten()
+            .chain(resolver_parents.iter().map(|rp| &rp.fields))
+            .flat_map(|ops| ops.0.iter().filter_map(|op| op.return_type.named_type()))
+            .collect::<HashSet<_>>();
+        for structure in structures.iter_mut() {
+            structure.operation_result = operation_results.contains(structure.name.orig());
+        }
+        // The nested selection sets apply to the types of the fields
+        let field_name_enums = structures
+            .iter()
+            .filter(|s| !s.is_input)
+            .map(|s| (s.name.orig().to_owned(), s.field_name_enum_ident()))
+            .collect::<HashMap<_, _>>();
+        for structure in structures.iter_mut().filter(|s| !s.is_input) {
+            structure.nested_field_names = structure
+                .fields
+                .iter()
+                .enumerate()
+                .filter_map(|(i, f)| {
+                    let enum_name = field_name_enums.get(f.field_type.named_type()?)?;
+                    Some((i, enum_name.clone()))
+                })
+                .collect();
+        }
+
+        // The generated field enums live next to the schema types
+        let type_idents = structures
+            .iter()
+            .map(|s| s.name.to_type_ident())
+            .chain(enums.iter().map(|e| e.name.to_type_ident()))
+            .chain(unions.iter().map(|u| u.name.to_type_ident()))
+            .chain(interfaces.iter().map(|i| i.name.to_type_ident()))
+            .collect::<HashSet<_>>();
+        for resolver_parent in resolver_parents.iter() {
+            let field_enum_name = resolver_parent.field_enum_name();
+            if type_idents.contains(&field_enum_name) {
+                errors.push(syn::Error::new(
+                    span,
+                    format!(
+                        "The `{field_enum_name}` enum of the field resolvers of `{}` clashes \
+                        with the schema type of the same name, rename one of them with \
+                        `name_override`",
+                        resolver_parent.name.orig()
+                    ),
+                ));
+            }
+        }
+        for structure in structures.iter().filter(|s| !s.is_input) {
+            let field_name_enum = structure.field_name_enum_ident();
+            if type_idents.contains(&field_name_enum) {
+                errors.push(syn::Error::new(
+                    span,
+                    format!(
+                        "The `{field_name_enum}` enum of the fields of `{}` clashes with the \
+                        schema type of the same name, rename one of them with `name_override`",
+                        structure.name.orig()
+                    ),
+                ));
+            }
+            let selected_fields_trait = structure.selected_fields_trait_ident();
+            if !structure.nested_field_names.is_empty()
+                && type_idents.contains(&selected_fields_trait)
+            {
+                errors.push(syn::Error::new(
+                    span,
+                    format!(
+                        "The `{selected_fields_trait}` trait of the selected fields of `{}` \
+                        clashes with the schema type of the same name, rename one of them with \
+                        `name_override`",
+                        structure.name.orig()
+                    ),
+                ));
+            }
+        }
+
+        // Mismatching default values are compile errors, the checked ones back `Default` and the
+        // builders of the input types
+        let checker = DefaultValueChecker::new(&enums, &structures);
+        let mut checked_defaults = vec![];
+        let inputs = structures
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.is_input)
+            .flat_map(|(i, s)| {
+                s.fields
+                    .iter()
+                    .enumerate()
+                    .map(move |(j, f)| ((i, j), s.name.orig(), f))
+            });
+        let args = [&queries, &mutations, &subscriptions]
+            .into_iter()
+            .flatten()
+            .chain(resolver_parents.iter().map(|rp| &rp.fields))
+            .flat_map(|ops| ops.0.iter())
+            .flat_map(|op| op.args.iter().map(move |arg| (op.name.orig(), arg)));
+        for (owner, field, index) in inputs
+            .map(|(index, owner, field)| (owner, field, Some(index)))
+            .chain(args.map(|(owner, arg)| (owner, arg, None)))
+        {
+            let Some(ref value) = field.default_value.value else {
+                continue;
+            };
+            match checker.check(value, &field.field_type) {
+                Ok(true) => checked_defaults.extend(index),
+                Ok(false) => (),
+                Err(e) => errors.push(syn::Error::new(
+                    span,
+                    format!(
+                        "The default value of `{owner}.{}` does not match its type `{}`: {e}",
+                        field.name.orig(),
+                        field.graphql_type
+                    ),
+                )),
+            }
+        }
+        for (i, j) in checked_defaults {
+            structures[i].fields[j].default_value.checked = true;
+        }
+
+        // Interfaces can only be resolved once all the structures are known
+        for interface in interfaces.iter_mut() {
+            match interface.resolve_implementors(&structures) {
+                Ok(_) => (),
+                Err(e) => errors.push(e),
+            };
+        }
+        box_recursive_types(&mut structures, &unions, &mut interfaces);
+        for structure in structures.iter() {
+            for interface_name in structure.implements.iter() {
+                if !interfaces.iter().any(|i| i.name.orig() == interface_name) {
+                    errors.push(syn::Error::new(
+                        span,
+                        format!(
+                            "Type `{}` implements unknown interface `{interface_name}`",
+                            structure.name.orig()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        // AppSync requires the subscriptions to return the payload of their mutations
+        for subscription in subscriptions.iter().flat_map(|ops| ops.0.iter()) {
+            for mutation_name in subscription.subscribed_mutations.0.iter() {
+                let mutation = mutations
+                    .iter()
+                    .flat_map(|ops| ops.0.iter())
+                    .find(|m| m.name.orig() == mutation_name);
+                let message = match mutation {
+                    None => format!("no mutation named `{mutation_name}`"),
+                    Some(mutation) if mutation.payload_type != subscription.payload_type => {
+                        format!(
+                            "the subscription returns `{}` but mutation `{mutation_name}` returns `{}`",
+                            subscription.payload_type, mutation.payload_type
+                        )
+                    }
+                    Some(_) => continue,
+                };
+                let (span, location) = schema_files.locate(subscription.position);
+                errors.push(syn::Error::new(
+                    span,
+                    format!(
+                        "`@aws_subscribe` of subscription `{}` ({location}): {message}",
+                        subscription.name.orig()
+                    ),
+                ));
+            }
+        }
+
         if !tos.is_empty() {
             errors.extend(
                 tos.into_values()
@@ -863,7 +3517,25 @@ impl GraphQLSchema {
                     .map(|to| {
                         syn::Error::new(
                             to.type_name().span(),
-                            format!("No type or input named `{}`", to.type_name()),
+                            format!(
+                                "No type, input, union or interface named `{}`",
+                                to.type_name()
+                            ),
+                        )
+                    }),
+            );
+        }
+        if !frs.is_empty() {
+            errors.extend(
+                frs.into_values()
+                    .flat_map(|frns| frns.into_values())
+                    .map(|fr| {
+                        syn::Error::new(
+                            fr.type_name().span(),
+                            format!(
+                                "No type named `{}`, field resolvers are only supported on non-root types",
+                                fr.type_name()
+                            ),
                         )
                     }),
             );
@@ -875,7 +3547,10 @@ impl GraphQLSchema {
                     .map(|no| {
                         syn::Error::new(
                             no.type_name().span(),
-                            format!("No type, enum or input named `{}`", no.type_name()),
+                            format!(
+                                "No type, enum, input, union or interface named `{}`",
+                                no.type_name()
+                            ),
                         )
                     }),
             );
@@ -887,6 +3562,10 @@ impl GraphQLSchema {
                 subscriptions: subscriptions.unwrap_or_default(),
                 structures,
                 enums,
+                unions,
+                interfaces,
+                resolver_parents,
+                check_responses: false,
             })
         } else {
             Err(errors
@@ -912,40 +3591,83 @@ impl GraphQLSchema {
             #(#structures)*
         });
     }
+    fn unions_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let unions = self.unions.iter();
+        let span = current_span();
+        tokens.extend(quote_spanned! {span=>
+            #(#unions)*
+        });
+    }
+    fn interfaces_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let interfaces = self.interfaces.iter();
+        let span = current_span();
+        tokens.extend(quote_spanned! {span=>
+            #(#interfaces)*
+        });
+    }
     fn operation_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let span = current_span();
         let query_field_name = OperationKind::Query.operation_enum_name(span);
-        let query_field_variants = self.queries.variants_iter();
+        let query_field_enum = self.queries.field_enum(&query_field_name);
         let mutation_field_name = OperationKind::Mutation.operation_enum_name(span);
-        let mutation_field_variants = self.mutations.variants_iter();
+        let mutation_field_enum = self.mutations.field_enum(&mutation_field_name);
         let subscription_field_name = OperationKind::Subscription.operation_enum_name(span);
-        let subscription_field_variants = self.subscriptions.variants_iter();
+        let subscription_field_enum = self.subscriptions.field_enum(&subscription_field_name);
+        let field_enums = self.resolver_parents.iter().map(ResolverParent::field_enum);
+        let resolver_parent_variants = self
+            .resolver_parents
+            .iter()
+            .map(ResolverParent::operation_variant);
         tokens.extend(quote_spanned! {span=>
-            #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Deserialize)]
-            #[serde(rename_all = "camelCase")]
-            pub enum #query_field_name {
-                #(#query_field_variants,)*
-            }
-            #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Deserialize)]
-            #[serde(rename_all = "camelCase")]
-            pub enum #mutation_field_name {
-                #(#mutation_field_variants,)*
-            }
-            #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Deserialize)]
-            #[serde(rename_all = "camelCase")]
-            pub enum #subscription_field_name {
-                #(#subscription_field_variants,)*
-            }
+            #query_field_enum
+            #mutation_field_enum
+            #subscription_field_enum
+            #(#field_enums)*
             #[derive(Debug, Clone, Copy, ::lambda_appsync::serde::Deserialize)]
             #[serde(tag = "parentTypeName", content = "fieldName")]
             pub enum Operation {
                 Query(#query_field_name),
                 Mutation(#mutation_field_name),
                 Subscription(#subscription_field_name),
+                #(#resolver_parent_variants,)*
             }
             use __operations::DefaultOperations;
             impl DefaultOperations for Operation {}
         });
+        self.mutation_subscriptions_to_tokens(tokens);
+    }
+    fn mutation_subscriptions_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = current_span();
+        let mutation_field_name = OperationKind::Mutation.operation_enum_name(span);
+        let subscription_field_name = OperationKind::Subscription.operation_enum_name(span);
+        let match_arms = self.mutations.0.iter().map(|mutation| {
+            let variant = mutation.name.to_type_ident();
+            let subscription_variants = self
+                .subscriptions
+                .0
+                .iter()
+                .filter(|subscription| {
+                    subscription
+                        .subscribed_mutations
+                        .contains(mutation.name.orig())
+                })
+                .map(|subscription| subscription.name.to_type_ident());
+            quote_spanned! {span=>
+                #mutation_field_name::#variant => &[#(#subscription_field_name::#subscription_variants),*]
+            }
+        });
+        tokens.extend(quote_spanned! {span=>
+            #[allow(deprecated)]
+            impl #mutation_field_name {
+                /// Subscriptions triggered by this mutation, i.e. listing it in the `mutations`
+                /// of their `@aws_subscribe` directive
+                pub fn subscriptions(&self) -> &'static [#subscription_field_name] {
+                    match *self {
+                        #(#match_arms,)*
+                    }
+                }
+            }
+        });
     }
     fn default_operations_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let query_field_default_ops = self.queries.default_op_iter(OperationKind::Query);
@@ -953,11 +3675,16 @@ impl GraphQLSchema {
         let subscription_field_default_ops = self
             .subscriptions
             .default_op_iter(OperationKind::Subscription);
+        let field_default_ops = self
+            .resolver_parents
+            .iter()
+            .flat_map(ResolverParent::default_op_iter);
         tokens.extend(quote_spanned! {current_span()=>
             pub(super) trait DefaultOperations {
                 #(#query_field_default_ops)*
                 #(#mutation_field_default_ops)*
                 #(#subscription_field_default_ops)*
+                #(#field_default_ops)*
             }
         });
     }
@@ -971,14 +3698,98 @@ impl GraphQLSchema {
         let subscription_field_execute_match_arm = self
             .subscriptions
             .execute_match_arm_iter(OperationKind::Subscription);
+        let field_execute_match_arm = self
+            .resolver_parents
+            .iter()
+            .map(ResolverParent::execute_match_arm);
         let span = current_span();
+        let (query_field_modes_match_arm, query_field_cognito_groups_match_arm) = self
+            .queries
+            .auth_match_arms(&OperationKind::Query.operation_enum_name(span));
+        let (mutation_field_modes_match_arm, mutation_field_cognito_groups_match_arm) = self
+            .mutations
+            .auth_match_arms(&OperationKind::Mutation.operation_enum_name(span));
+        let (subscription_field_modes_match_arm, subscription_field_cognito_groups_match_arm) =
+            self.subscriptions
+                .auth_match_arms(&OperationKind::Subscription.operation_enum_name(span));
+        let (field_modes_match_arm, field_cognito_groups_match_arm): (Vec<_>, Vec<_>) = self
+            .resolver_parents
+            .iter()
+            .map(ResolverParent::auth_match_arms)
+            .unzip();
+        let execute_match = quote_spanned! {span=>
+            match self {
+                Operation::Query(query_field) => match query_field {
+                    #(#query_field_execute_match_arm,)*
+                },
+                Operation::Mutation(mutation_field) => match mutation_field {
+                    #(#mutation_field_execute_match_arm,)*
+                },
+                Operation::Subscription(subscription_field) => match subscription_field {
+                    #(#subscription_field_execute_match_arm,)*
+                },
+                #(#field_execute_match_arm,)*
+            }
+        };
+        let execute_body = if self.check_responses {
+            quote_spanned! {span=>
+                let response = #execute_match;
+                response.and_then(|partial| {
+                    // Null data along with an error is how a resolver reports a failed field
+                    if partial.error.is_none() || !partial.data.is_null() {
+                        self.check_response(&partial.data)?;
+                    }
+                    ::core::result::Result::Ok(partial)
+                })
+            }
+        } else {
+            execute_match
+        };
         tokens.extend(quote_spanned! {span=>
+            #[allow(deprecated)]
             impl Operation {
+                /// Authorization modes allowed by the AppSync authorization directives of the
+                /// operation, empty when the default authorization mode of the API applies
+                pub fn allowed_auth_modes(&self) -> &'static [::lambda_appsync::AppsyncAuthMode] {
+                    match *self {
+                        Operation::Query(query_field) => match query_field {
+                            #(#query_field_modes_match_arm,)*
+                        },
+                        Operation::Mutation(mutation_field) => match mutation_field {
+                            #(#mutation_field_modes_match_arm,)*
+                        },
+                        Operation::Subscription(subscription_field) => match subscription_field {
+                            #(#subscription_field_modes_match_arm,)*
+                        },
+                        #(#field_modes_match_arm,)*
+                    }
+                }
+                /// Cognito groups of the `cognito_groups` arguments of the AppSync authorization
+                /// directives of the operation, a Cognito user must belong to one of them if any
+                pub fn required_cognito_groups(&self) -> &'static [&'static str] {
+                    match *self {
+                        Operation::Query(query_field) => match query_field {
+                            #(#query_field_cognito_groups_match_arm,)*
+                        },
+                        Operation::Mutation(mutation_field) => match mutation_field {
+                            #(#mutation_field_cognito_groups_match_arm,)*
+                        },
+                        Operation::Subscription(subscription_field) => match subscription_field {
+                            #(#subscription_field_cognito_groups_match_arm,)*
+                        },
+                        #(#field_cognito_groups_match_arm,)*
+                    }
+                }
                 async fn execute(self,
                     event: ::lambda_appsync::AppsyncEvent<Self>
                 ) -> ::lambda_appsync::AppsyncResponse {
                     match self._execute(event).await {
-                        ::core::result::Result::Ok(v) => v.into(),
+                        ::core::result::Result::Ok(partial) => {
+                            if let ::core::option::Option::Some(ref e) = partial.error {
+                                ::lambda_appsync::log::error!("{e}");
+                            }
+                            partial.into()
+                        }
                         ::core::result::Result::Err(e) => {
                             ::lambda_appsync::log::error!("{e}");
                             e.into()
@@ -988,32 +3799,121 @@ impl GraphQLSchema {
                 async fn _execute(
                     self,
                     event: ::lambda_appsync::AppsyncEvent<Self>
-                ) -> ::core::result::Result<::lambda_appsync::serde_json::Value, ::lambda_appsync::AppsyncError> {
-                    match self {
+                ) -> ::core::result::Result<::lambda_appsync::PartialData<::lambda_appsync::serde_json::Value>, ::lambda_appsync::AppsyncError> {
+                    #execute_body
+                }
+            }
+        });
+        if self.check_responses {
+            self.check_response_to_tokens(tokens);
+        }
+    }
+    // The `check_response` method of `Operation`, checking the result of an operation against
+    // its GraphQL type, and the statics describing the object types it refers to
+    fn check_response_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = current_span();
+        let mut abstracts = HashMap::new();
+        for r_union in self.unions.iter() {
+            abstracts.insert(
+                r_union.name.orig(),
+                r_union.members.iter().map(|m| m.name.orig()).collect(),
+            );
+        }
+        for interface in self.interfaces.iter() {
+            abstracts.insert(
+                interface.name.orig(),
+                interface
+                    .implementors
+                    .iter()
+                    .map(|i| i.type_name.as_str())
+                    .collect(),
+            );
+        }
+        let objects = self
+            .structures
+            .iter()
+            .filter(|s| !s.is_input)
+            .collect::<Vec<_>>();
+        let output_types = OutputTypes {
+            objects: objects.iter().map(|s| s.name.orig()).collect(),
+            abstracts,
+        };
+        let object_statics = objects.iter().map(|s| {
+            let type_name = s.name.orig();
+            let object_ident = format_ident!("{}", type_name, span = span);
+            let field_names = s.fields.iter().map(|f| f.name.orig());
+            let field_types = s
+                .fields
+                .iter()
+                .map(|f| output_types.to_tokens(&f.graphql_type));
+            quote_spanned! {span=>
+                pub(super) static #object_ident: ::lambda_appsync::ObjectType = ::lambda_appsync::ObjectType {
+                    name: #type_name,
+                    fields: &[#((#field_names, #field_types)),*],
+                };
+            }
+        });
+        let query_field_match_arms = self.queries.output_type_match_arms(
+            &OperationKind::Query.operation_enum_name(span),
+            &output_types,
+        );
+        let mutation_field_match_arms = self.mutations.output_type_match_arms(
+            &OperationKind::Mutation.operation_enum_name(span),
+            &output_types,
+        );
+        let field_match_arms = self
+            .resolver_parents
+            .iter()
+            .map(|rp| rp.output_type_match_arm(&output_types));
+        tokens.extend(quote_spanned! {span=>
+            #[allow(non_upper_case_globals)]
+            mod __output_types {
+                // The statics refer to each other with the same paths as the operations
+                #[allow(unused_imports)]
+                use super::__output_types;
+                #(#object_statics)*
+            }
+            impl Operation {
+                fn check_response(
+                    self,
+                    value: &::lambda_appsync::serde_json::Value,
+                ) -> ::core::result::Result<(), ::lambda_appsync::AppsyncError> {
+                    let (field_name, output_type): (&str, &::lambda_appsync::OutputType) = match self {
                         Operation::Query(query_field) => match query_field {
-                            #(#query_field_execute_match_arm,)*
+                            #(#query_field_match_arms,)*
                         },
                         Operation::Mutation(mutation_field) => match mutation_field {
-                            #(#mutation_field_execute_match_arm,)*
-                        },
-                        Operation::Subscription(subscription_field) => match subscription_field {
-                            #(#subscription_field_execute_match_arm,)*
+                            #(#mutation_field_match_arms,)*
                         },
-                    }
+                        // The result of a subscription is its filter, not its GraphQL type
+                        Operation::Subscription(_) => return ::core::result::Result::Ok(()),
+                        #(#field_match_arms,)*
+                    };
+                    output_type.check(value, field_name)
                 }
             }
         });
     }
-    fn operations_module_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+    fn operations_module_to_tokens(
+        &self,
+        tokens: &mut proc_macro2::TokenStream,
+        warn_deprecated: bool,
+    ) {
         let mut default_operations_trait = proc_macro2::TokenStream::new();
         self.default_operations_to_tokens(&mut default_operations_trait);
-        let query_operation_module_iter = self.queries.operation_module_iter(OperationKind::Query);
+        let query_operation_module_iter = self
+            .queries
+            .operation_module_iter(OperationKind::Query, warn_deprecated);
         let mutation_operation_module_iter = self
             .mutations
-            .operation_module_iter(OperationKind::Mutation);
+            .operation_module_iter(OperationKind::Mutation, warn_deprecated);
         let subscription_operation_module_iter = self
             .subscriptions
-            .operation_module_iter(OperationKind::Subscription);
+            .operation_module_iter(OperationKind::Subscription, warn_deprecated);
+        let field_operation_module_iter = self
+            .resolver_parents
+            .iter()
+            .map(|rp| rp.operation_module(warn_deprecated));
         tokens.extend(quote! {
             #[allow(dead_code)]
             mod __operations {
@@ -1028,15 +3928,130 @@ impl GraphQLSchema {
                 pub(crate) mod subscriptions {
                     #(#subscription_operation_module_iter)*
                 }
+                pub(crate) mod fields {
+                    #(#field_operation_module_iter)*
+                }
             }
         });
     }
+    // The results of the operations are checked against their GraphQL type before responding
+    pub(crate) fn check_responses(&mut self) {
+        self.check_responses = true;
+    }
+    // Every `type` and `input` struct gets a builder
+    pub(crate) fn generate_builders(&mut self) {
+        for structure in self.structures.iter_mut() {
+            structure.builder = true;
+        }
+    }
+    // Nullable input fields and operation arguments become `Maybe<T>` instead of `Option<T>`
+    pub(crate) fn use_maybe_for_nullable_inputs(&mut self) {
+        let input_fields = self
+            .structures
+            .iter_mut()
+            .filter(|s| s.is_input)
+            .flat_map(|s| s.fields.iter_mut());
+        let args = [
+            &mut self.queries,
+            &mut self.mutations,
+            &mut self.subscriptions,
+        ]
+        .into_iter()
+        .chain(self.resolver_parents.iter_mut().map(|rp| &mut rp.fields))
+        .flat_map(|ops| ops.0.iter_mut())
+        .flat_map(|op| op.args.iter_mut())
+        // The arguments with a default value are never omitted once extracted
+        .filter(|arg| !arg.default_value.is_set());
+        for field in input_fields.chain(args) {
+            field.field_type.set_maybe();
+        }
+    }
+    // Adds the derives of the `derive_all` option to every generated type, then those of the
+    // `derive` and `attr` options to the types they name
+    pub(crate) fn apply_type_attributes(
+        &mut self,
+        mut tas: super::TypeAttributesMap,
+        derive_all: Vec<syn::Path>,
+    ) -> Result<(), syn::Error> {
+        const ENUM_DERIVES: &[&str] = &[
+            "Debug",
+            "Clone",
+            "Copy",
+            "Serialize",
+            "Deserialize",
+            "PartialEq",
+            "Eq",
+            "PartialOrd",
+            "Ord",
+            "Hash",
+        ];
+        const UNION_DERIVES: &[&str] = &["Debug", "Clone", "Serialize", "Deserialize"];
+        // `Default` cannot be derived on enums without a default variant
+        let enum_derive_all = derive_all
+            .iter()
+            .filter(|path| !path.is_ident("Default"))
+            .collect::<Vec<_>>();
+        for structure in self.structures.iter_mut() {
+            let generated_derives = structure.generated_derives();
+            let extra_attributes = &mut structure.extra_attributes;
+            extra_attributes.add_derives(derive_all.iter(), generated_derives);
+            if let Some(ta) = tas.remove(structure.name.orig()) {
+                extra_attributes.add(&ta, generated_derives);
+            }
+        }
+        for r_enum in self.enums.iter_mut() {
+            let extra_attributes = &mut r_enum.extra_attributes;
+            extra_attributes.add_derives(enum_derive_all.iter().copied(), ENUM_DERIVES);
+            if let Some(ta) = tas.remove(r_enum.name.orig()) {
+                extra_attributes.add(&ta, ENUM_DERIVES);
+            }
+        }
+        for r_union in self.unions.iter_mut() {
+            let extra_attributes = &mut r_union.extra_attributes;
+            extra_attributes.add_derives(enum_derive_all.iter().copied(), UNION_DERIVES);
+            if let Some(ta) = tas.remove(r_union.name.orig()) {
+                extra_attributes.add(&ta, UNION_DERIVES);
+            }
+        }
+        for interface in self.interfaces.iter_mut() {
+            let extra_attributes = &mut interface.extra_attributes;
+            extra_attributes.add_derives(enum_derive_all.iter().copied(), UNION_DERIVES);
+            if let Some(ta) = tas.remove(interface.name.orig()) {
+                extra_attributes.add(&ta, UNION_DERIVES);
+            }
+        }
+        if let Some(error) = tas
+            .into_values()
+            .map(|ta| {
+                syn::Error::new(
+                    ta.type_name().span(),
+                    format!(
+                        "No type, input, enum, union or interface named `{}`",
+                        ta.type_name()
+                    ),
+                )
+            })
+            .reduce(|mut acc, e| {
+                acc.combine(e);
+                acc
+            })
+        {
+            return Err(error);
+        }
+        Ok(())
+    }
     pub(crate) fn appsync_types_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         self.enums_to_tokens(tokens);
         self.structs_to_tokens(tokens);
+        self.unions_to_tokens(tokens);
+        self.interfaces_to_tokens(tokens);
     }
-    pub(crate) fn appsync_operations_to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        self.operations_module_to_tokens(tokens);
+    pub(crate) fn appsync_operations_to_tokens(
+        &self,
+        tokens: &mut proc_macro2::TokenStream,
+        warn_deprecated_operations: bool,
+    ) {
+        self.operations_module_to_tokens(tokens, warn_deprecated_operations);
         self.operation_to_tokens(tokens);
         self.impl_operation_to_tokens(tokens);
     }
@@ -1,14 +1,18 @@
 mod graphql;
 mod overrides;
+mod schema_files;
 
 use std::collections::HashMap;
 
 use graphql::GraphQLSchema;
-use overrides::{NameOverride, TypeOverride};
+use overrides::{
+    parse_derive_paths, FieldResolver, NameOverride, ScalarMap, TypeAttributes, TypeOverride,
+};
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
-use syn::{parenthesized, parse::Parse, parse_macro_input, LitBool, LitStr, Token, Type};
+use schema_files::{SchemaFiles, SchemaPaths};
+use syn::{parenthesized, parse::Parse, parse_macro_input, LitBool, Token, Type};
 
 struct AWSClient {
     fct_identifier: Ident,
@@ -60,7 +64,38 @@ impl AWSClient {
     }
 }
 
+// Captures error_policy = passthrough | sanitize | sanitize("Type1", "Type2", ...)
+enum ErrorPolicy {
+    // Errors are returned to the clients as they are
+    Passthrough,
+    // Errors are replaced by a generic error, except for the allowed types
+    Sanitize(Vec<syn::LitStr>),
+}
+impl Parse for ErrorPolicy {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        match ident.to_string().as_str() {
+            "passthrough" => Ok(Self::Passthrough),
+            "sanitize" => {
+                if !input.peek(syn::token::Paren) {
+                    return Ok(Self::Sanitize(vec![]));
+                }
+                let content;
+                _ = parenthesized!(content in input);
+                let allowed_types =
+                    content.parse_terminated(<syn::LitStr as Parse>::parse, Token![,])?;
+                Ok(Self::Sanitize(allowed_types.into_iter().collect()))
+            }
+            _ => Err(syn::Error::new(
+                ident.span(),
+                "Unknown error policy, expected `passthrough` or `sanitize`",
+            )),
+        }
+    }
+}
+
 // I suppose this is acceptable for a proc-macro
+#[allow(clippy::large_enum_variant)]
 enum OptionalParameter {
     Batch(bool),
     ExcludeLambdaHandler(bool),
@@ -70,8 +105,20 @@ enum OptionalParameter {
     ExcludeAppsyncOperations(bool),
     OnlyAppsyncOperations(bool),
     Hook(Ident),
+    AfterHook(Ident),
+    WarnDeprecatedOperations(bool),
+    CheckAuthDirectives(bool),
+    NullableInputsAsMaybe(bool),
+    CheckResponses(bool),
+    ErrorPolicy(ErrorPolicy),
+    Builders(bool),
+    Derive(TypeAttributes),
+    DeriveAll(Vec<syn::Path>),
+    Attr(TypeAttributes),
     TypeOverride(TypeOverride),
     NameOverride(NameOverride),
+    ScalarMap(ScalarMap),
+    FieldResolver(FieldResolver),
 }
 impl Parse for OptionalParameter {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -94,8 +141,26 @@ impl Parse for OptionalParameter {
                 input.parse::<LitBool>()?.value(),
             )),
             "hook" => Ok(Self::Hook(input.parse()?)),
+            "after_hook" => Ok(Self::AfterHook(input.parse()?)),
+            "warn_deprecated_operations" => Ok(Self::WarnDeprecatedOperations(
+                input.parse::<LitBool>()?.value(),
+            )),
+            "check_auth_directives" => {
+                Ok(Self::CheckAuthDirectives(input.parse::<LitBool>()?.value()))
+            }
+            "nullable_inputs_as_maybe" => Ok(Self::NullableInputsAsMaybe(
+                input.parse::<LitBool>()?.value(),
+            )),
+            "check_responses" => Ok(Self::CheckResponses(input.parse::<LitBool>()?.value())),
+            "error_policy" => Ok(Self::ErrorPolicy(input.parse()?)),
+            "builders" => Ok(Self::Builders(input.parse::<LitBool>()?.value())),
+            "derive" => Ok(Self::Derive(TypeAttributes::parse_derives(input)?)),
+            "derive_all" => Ok(Self::DeriveAll(parse_derive_paths(input)?)),
+            "attr" => Ok(Self::Attr(TypeAttributes::parse_attrs(input)?)),
             "type_override" => Ok(Self::TypeOverride(input.parse()?)),
             "name_override" => Ok(Self::NameOverride(input.parse()?)),
+            "scalar_map" => Ok(Self::ScalarMap(input.parse()?)),
+            "field_resolver" => Ok(Self::FieldResolver(input.parse()?)),
             // Deprecated options
             "field_type_override" => Ok(Self::TypeOverride(input.parse()?)),
             // Unknown option
@@ -137,10 +202,26 @@ type TypeNameOverride = (Option<NameOverride>, FieldNameOverrides);
 // Maps field names to their name overrides for a field
 type FieldNameOverrides = HashMap<FieldName, NameOverride>;
 
-// [Type|Field|Arg]Name are just String
+// Captures scalar_map = Scalar: CustomType options
+// Maps custom GraphQL scalar names to the Rust type to use everywhere they appear
+type ScalarMaps = HashMap<ScalarName, ScalarMap>;
+
+// Captures field_resolver = Type.field options
+// using a HashMap hierarchy of TypeName -> FieldName -> FieldResolver
+type FieldResolvers = HashMap<TypeName, FieldResolverNames>;
+
+// For each type, maps the names of the fields resolved by the Lambda to their option
+type FieldResolverNames = HashMap<FieldName, FieldResolver>;
+
+// Captures derive = Type: Derive, ... and attr = Type: #[attribute] options
+// Maps GraphQL type names to the extra derives and attributes of the generated type
+type TypeAttributesMap = HashMap<TypeName, TypeAttributes>;
+
+// [Type|Field|Arg|Scalar]Name are just String
 type TypeName = String;
 type FieldName = String;
 type ArgName = String;
+type ScalarName = String;
 
 struct OptionalParameters {
     batch: bool,
@@ -148,8 +229,19 @@ struct OptionalParameters {
     appsync_operations: bool,
     lambda_handler: bool,
     hook: Option<Ident>,
+    after_hook: Option<Ident>,
+    warn_deprecated_operations: bool,
+    check_auth_directives: bool,
+    nullable_inputs_as_maybe: bool,
+    check_responses: bool,
+    error_policy: ErrorPolicy,
+    builders: bool,
+    derive_all: Vec<syn::Path>,
+    tas: TypeAttributesMap,
     tos: TypeOverrides,
     nos: NameOverrides,
+    sms: ScalarMaps,
+    frs: FieldResolvers,
 }
 impl Default for OptionalParameters {
     fn default() -> Self {
@@ -159,13 +251,24 @@ impl Default for OptionalParameters {
             appsync_operations: true,
             lambda_handler: true,
             hook: None,
+            after_hook: None,
+            warn_deprecated_operations: false,
+            check_auth_directives: false,
+            nullable_inputs_as_maybe: false,
+            check_responses: false,
+            error_policy: ErrorPolicy::Passthrough,
+            builders: false,
+            derive_all: vec![],
+            tas: TypeAttributesMap::new(),
             tos: TypeOverrides::new(),
             nos: NameOverrides::new(),
+            sms: ScalarMaps::new(),
+            frs: FieldResolvers::new(),
         }
     }
 }
 impl OptionalParameters {
-    fn set(&mut self, p: OptionalParameter) {
+    fn set(&mut self, p: OptionalParameter) -> syn::Result<()> {
         match p {
             OptionalParameter::Batch(batch) => self.batch = batch,
             OptionalParameter::ExcludeLambdaHandler(b) if b => self.lambda_handler = false,
@@ -189,6 +292,26 @@ impl OptionalParameters {
             OptionalParameter::Hook(ident) => {
                 self.hook.replace(ident);
             }
+            OptionalParameter::AfterHook(ident) => {
+                self.after_hook.replace(ident);
+            }
+            OptionalParameter::WarnDeprecatedOperations(b) => self.warn_deprecated_operations = b,
+            OptionalParameter::CheckAuthDirectives(b) => self.check_auth_directives = b,
+            OptionalParameter::NullableInputsAsMaybe(b) => self.nullable_inputs_as_maybe = b,
+            OptionalParameter::CheckResponses(b) => self.check_responses = b,
+            OptionalParameter::ErrorPolicy(ep) => self.error_policy = ep,
+            OptionalParameter::Builders(b) => self.builders = b,
+            OptionalParameter::Derive(ta) | OptionalParameter::Attr(ta) => {
+                match self.tas.entry(ta.type_name().to_string()) {
+                    std::collections::hash_map::Entry::Occupied(mut entry) => {
+                        entry.get_mut().merge(ta)
+                    }
+                    std::collections::hash_map::Entry::Vacant(entry) => {
+                        entry.insert(ta);
+                    }
+                }
+            }
+            OptionalParameter::DeriveAll(derives) => self.derive_all.extend(derives),
             OptionalParameter::TypeOverride(to) => {
                 // Retrieve the entry corresponding to `Type.field`
                 let to_field_entry = self
@@ -220,6 +343,26 @@ impl OptionalParameters {
                     no_type_entry.0.replace(no);
                 }
             }
+            OptionalParameter::ScalarMap(sm) => {
+                // A scalar has a single Rust type, the second one would silently win
+                match self.sms.entry(sm.scalar_name().to_string()) {
+                    std::collections::hash_map::Entry::Occupied(_) => {
+                        return Err(syn::Error::new(
+                            sm.scalar_name().span(),
+                            format!("Duplicate `scalar_map` for scalar `{}`", sm.scalar_name()),
+                        ));
+                    }
+                    std::collections::hash_map::Entry::Vacant(entry) => {
+                        entry.insert(sm);
+                    }
+                }
+            }
+            OptionalParameter::FieldResolver(fr) => {
+                self.frs
+                    .entry(fr.type_name().to_string())
+                    .or_default()
+                    .insert(fr.field_name().to_string(), fr);
+            }
             OptionalParameter::ExcludeLambdaHandler(_)
             | OptionalParameter::OnlyLambdaHandler(_)
             | OptionalParameter::ExcludeAppsyncTypes(_)
@@ -227,10 +370,12 @@ impl OptionalParameters {
             | OptionalParameter::ExcludeAppsyncOperations(_)
             | OptionalParameter::OnlyAppsyncOperations(_) => (),
         }
+        Ok(())
     }
 }
 
 struct AppsyncLambdaMain {
+    schema_files: SchemaFiles,
     graphql_schema: GraphQLSchema,
     aws_clients: Vec<AWSClient>,
     options: OptionalParameters,
@@ -238,33 +383,12 @@ struct AppsyncLambdaMain {
 
 impl Parse for AppsyncLambdaMain {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let graphql_schema_path = input.parse::<LitStr>()?;
-        let path_value = graphql_schema_path.value();
-        let full_path = if std::path::Path::new(&path_value).is_relative() {
-            std::env::current_dir()
-                .map_err(|e| {
-                    syn::Error::new(
-                        graphql_schema_path.span(),
-                        format!("Could not get current directory: {e}"),
-                    )
-                })?
-                .join(&path_value)
-        } else {
-            std::path::PathBuf::from(path_value)
-        };
-        let schema_str = std::fs::read_to_string(&full_path).map_err(|e| {
-            syn::Error::new(
-                graphql_schema_path.span(),
-                format!(
-                    "Could not open GraphQL schema file at '{}' ({e})",
-                    full_path.display()
-                ),
-            )
-        })?;
-        let schema = graphql_parser::parse_schema(&schema_str)
+        let schema_paths = input.parse::<SchemaPaths>()?;
+        let schema_files = SchemaFiles::load(&schema_paths)?;
+        let schema = graphql_parser::parse_schema(schema_files.content())
             .map_err(|e| {
                 syn::Error::new(
-                    graphql_schema_path.span(),
+                    schema_paths.span(),
                     format!("Could not parse GraphQL schema file ({e})",),
                 )
             })?
@@ -280,7 +404,7 @@ impl Parse for AppsyncLambdaMain {
             }
             if input.peek(syn::Ident) && input.peek2(Token![=]) {
                 // That's a parameter
-                options.set(input.parse()?);
+                options.set(input.parse()?)?;
             } else if AWSClient::is_next(input) {
                 aws_clients.push(input.parse::<AWSClient>()?);
             } else {
@@ -288,14 +412,31 @@ impl Parse for AppsyncLambdaMain {
             }
         }
 
-        let graphql_schema = GraphQLSchema::new(
+        let mut graphql_schema = GraphQLSchema::new(
             schema,
-            graphql_schema_path.span(),
+            schema_paths.span(),
+            &schema_files,
             std::mem::take(&mut options.tos),
             std::mem::take(&mut options.nos),
+            std::mem::take(&mut options.sms),
+            std::mem::take(&mut options.frs),
+        )?;
+        if options.nullable_inputs_as_maybe {
+            graphql_schema.use_maybe_for_nullable_inputs();
+        }
+        if options.check_responses {
+            graphql_schema.check_responses();
+        }
+        if options.builders {
+            graphql_schema.generate_builders();
+        }
+        graphql_schema.apply_type_attributes(
+            std::mem::take(&mut options.tas),
+            std::mem::take(&mut options.derive_all),
         )?;
 
         Ok(Self {
+            schema_files,
             graphql_schema,
             aws_clients,
             options,
@@ -326,14 +467,70 @@ impl AppsyncLambdaMain {
         } else {
             quote! {}
         };
+        let check_auth = if self.options.check_auth_directives {
+            quote! {
+                if let ::core::result::Result::Err(e) = event.identity.check_auth(
+                    event.info.operation.allowed_auth_modes(),
+                    event.info.operation.required_cognito_groups(),
+                ) {
+                    ::lambda_appsync::log::error!("{e}");
+                    return e.into();
+                }
+            }
+        } else {
+            quote! {}
+        };
+        let execute = if let Some(ref after_hook) = self.options.after_hook {
+            let call_after_hook = quote_spanned! {after_hook.span()=>
+                mod _check_after_sig {
+                    use super::Operation;
+                    use ::lambda_appsync::{AppsyncIdentity, AppsyncResponse};
+                    use ::core::future::Future;
+                    #[inline(always)]
+                    pub(super) async fn call_after_hook<'a, Fut, H>(
+                        after_hook: H,
+                        operation: &'a Operation,
+                        identity: &'a AppsyncIdentity,
+                        response: AppsyncResponse,
+                    ) -> AppsyncResponse
+                    where
+                        Fut: Future<Output = AppsyncResponse>,
+                        H: Fn(&'a Operation, &'a AppsyncIdentity, AppsyncResponse) -> Fut {
+                        after_hook(operation, identity, response).await
+                    }
+                }
+                _check_after_sig::call_after_hook(#after_hook, &operation, &identity, response).await
+            };
+            // The event is consumed by the execution of the operation
+            quote! {
+                let operation = event.info.operation;
+                let identity = event.identity.clone();
+                let response = event.info.operation.execute(event).await;
+                let response = { #call_after_hook };
+            }
+        } else {
+            quote! {
+                let response = event.info.operation.execute(event).await;
+            }
+        };
+        let sanitize_error = match self.options.error_policy {
+            ErrorPolicy::Passthrough => quote! {},
+            ErrorPolicy::Sanitize(ref allowed_types) => quote! {
+                .sanitize_error(&[#(#allowed_types),*])
+            },
+        };
         tokens.extend(quote! {
             async fn appsync_handler(event: ::lambda_appsync::AppsyncEvent<Operation>) -> ::lambda_appsync::AppsyncResponse {
                 ::lambda_appsync::log::info!("event={event:?}");
                 ::lambda_appsync::log::info!("operation={:?}", event.info.operation);
 
+                #check_auth
+
                 #call_hook
 
-                event.info.operation.execute(event).await
+                #execute
+
+                response #sanitize_error
             }
         });
         if self.options.batch {
@@ -411,11 +608,13 @@ impl AppsyncLambdaMain {
 
 impl ToTokens for AppsyncLambdaMain {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.schema_files.to_tokens(tokens);
         if self.options.appsync_types {
             self.graphql_schema.appsync_types_to_tokens(tokens);
         }
         if self.options.appsync_operations {
-            self.graphql_schema.appsync_operations_to_tokens(tokens);
+            self.graphql_schema
+                .appsync_operations_to_tokens(tokens, self.options.warn_deprecated_operations);
         }
         if self.options.lambda_handler {
             self.appsync_event_handler(tokens);
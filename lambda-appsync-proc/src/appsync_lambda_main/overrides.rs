@@ -1,5 +1,6 @@
 use syn::ext::IdentExt;
 
+#[derive(Clone)]
 pub(super) struct TypeOverride {
     type_name: syn::Ident,
     field_name: syn::Ident,
@@ -19,6 +20,9 @@ impl TypeOverride {
     pub(super) fn type_ident(self) -> syn::Type {
         self.type_ident
     }
+    pub(super) fn type_ident_ref(&self) -> &syn::Type {
+        &self.type_ident
+    }
 }
 impl syn::parse::Parse for TypeOverride {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -79,3 +83,120 @@ impl syn::parse::Parse for NameOverride {
         })
     }
 }
+
+pub(super) struct ScalarMap {
+    scalar_name: syn::Ident,
+    type_ident: syn::Type,
+}
+impl ScalarMap {
+    pub(super) fn scalar_name(&self) -> &syn::Ident {
+        &self.scalar_name
+    }
+    pub(super) fn type_ident(&self) -> &syn::Type {
+        &self.type_ident
+    }
+}
+impl syn::parse::Parse for ScalarMap {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let scalar_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![:]>()?;
+        let type_ident = input
+            .parse()
+            .map_err(|e| syn::Error::new(e.span(), "Expected a Type (struct, enum, etc...)"))?;
+        Ok(Self {
+            scalar_name,
+            type_ident,
+        })
+    }
+}
+
+pub(super) struct FieldResolver {
+    type_name: syn::Ident,
+    field_name: syn::Ident,
+}
+impl FieldResolver {
+    pub(super) fn type_name(&self) -> &syn::Ident {
+        &self.type_name
+    }
+    pub(super) fn field_name(&self) -> &syn::Ident {
+        &self.field_name
+    }
+}
+impl syn::parse::Parse for FieldResolver {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let type_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![.]>()?;
+        let field_name = input.call(syn::Ident::parse_any)?;
+        Ok(Self {
+            type_name,
+            field_name,
+        })
+    }
+}
+
+// Parses `Derive, path::to::Derive, ...`. Like the options of the macro, the paths are separated
+// by commas, so the list stops before the next option (`name = ...`) or AWS client (`name() -> ...`)
+pub(super) fn parse_derive_paths(input: syn::parse::ParseStream) -> syn::Result<Vec<syn::Path>> {
+    let mut paths = vec![input.parse::<syn::Path>()?];
+    while input.peek(syn::Token![,]) {
+        let fork = input.fork();
+        _ = fork.parse::<syn::Token![,]>()?;
+        if fork.parse::<syn::Path>().is_err()
+            || fork.peek(syn::Token![=])
+            || fork.peek(syn::token::Paren)
+        {
+            break;
+        }
+        _ = input.parse::<syn::Token![,]>()?;
+        paths.push(input.parse::<syn::Path>()?);
+    }
+    Ok(paths)
+}
+
+// Captures derive = Type: Derive, ... and attr = Type: #[attribute] options
+pub(super) struct TypeAttributes {
+    type_name: syn::Ident,
+    derives: Vec<syn::Path>,
+    attrs: Vec<syn::Attribute>,
+}
+impl TypeAttributes {
+    pub(super) fn type_name(&self) -> &syn::Ident {
+        &self.type_name
+    }
+    pub(super) fn derives(&self) -> &[syn::Path] {
+        &self.derives
+    }
+    pub(super) fn attrs(&self) -> &[syn::Attribute] {
+        &self.attrs
+    }
+    pub(super) fn merge(&mut self, other: Self) {
+        self.derives.extend(other.derives);
+        self.attrs.extend(other.attrs);
+    }
+    pub(super) fn parse_derives(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let type_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![:]>()?;
+        let derives = parse_derive_paths(input)?;
+        Ok(Self {
+            type_name,
+            derives,
+            attrs: vec![],
+        })
+    }
+    pub(super) fn parse_attrs(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let type_name = input.call(syn::Ident::parse_any)?;
+        _ = input.parse::<syn::Token![:]>()?;
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        if attrs.is_empty() {
+            return Err(syn::Error::new(
+                input.span(),
+                "Expected an attribute (`#[...]`)",
+            ));
+        }
+        Ok(Self {
+            type_name,
+            derives: vec![],
+            attrs,
+        })
+    }
+}
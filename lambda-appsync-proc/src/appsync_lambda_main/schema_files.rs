@@ -0,0 +1,366 @@
+use std::path::PathBuf;
+
+use graphql_parser::Pos;
+use proc_macro2::Span;
+use quote::{quote, ToTokens};
+use syn::{bracketed, parenthesized, punctuated::Punctuated, LitStr, Token};
+
+// The schema path argument: a single path or a list of paths, each of them being
+// a file, a directory of `.graphql`/`.gql` files or a glob pattern
+pub(super) struct SchemaPaths {
+    paths: Vec<LitStr>,
+    // Environment variables read by the `env!` macros of the paths
+    env_vars: Vec<String>,
+    span: Span,
+}
+impl SchemaPaths {
+    pub(super) fn span(&self) -> Span {
+        self.span
+    }
+}
+impl syn::parse::Parse for SchemaPaths {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::token::Bracket) {
+            let content;
+            let bracket = bracketed!(content in input);
+            let (paths, env_vars): (Vec<_>, Vec<_>) =
+                Punctuated::<SchemaPath, Token![,]>::parse_terminated(&content)?
+                    .into_iter()
+                    .map(|path| (path.path, path.env_vars))
+                    .unzip();
+            if paths.is_empty() {
+                return Err(syn::Error::new(
+                    bracket.span.join(),
+                    "Expected at least one GraphQL schema path",
+                ));
+            }
+            Ok(Self {
+                paths,
+                env_vars: env_vars.concat(),
+                span: bracket.span.join(),
+            })
+        } else {
+            let SchemaPath { path, env_vars } = input.parse::<SchemaPath>()?;
+            let span = path.span();
+            Ok(Self {
+                paths: vec![path],
+                env_vars,
+                span,
+            })
+        }
+    }
+}
+
+// A string literal, or the `env!` and `concat!` macros usually used to build paths, which
+// are evaluated here as a proc macro receives its input before they are expanded
+struct SchemaPath {
+    path: LitStr,
+    // Environment variables read by the `env!` macros
+    env_vars: Vec<String>,
+}
+impl syn::parse::Parse for SchemaPath {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(Self {
+                path: input.parse()?,
+                env_vars: vec![],
+            });
+        }
+        let not_a_path = |span| {
+            syn::Error::new(
+                span,
+                "Expected a string literal, `env!(\"VAR\")` or `concat!(...)`",
+            )
+        };
+        if !(input.peek(syn::Ident) && input.peek2(Token![!])) {
+            return Err(not_a_path(input.span()));
+        }
+        let macro_name = input.parse::<syn::Ident>()?;
+        _ = input.parse::<Token![!]>()?;
+        let content;
+        _ = parenthesized!(content in input);
+        let mut env_vars = vec![];
+        let value = match macro_name.to_string().as_str() {
+            "env" => {
+                let var_name = content.parse::<LitStr>()?;
+                // Optional custom error message
+                if content.peek(Token![,]) {
+                    _ = content.parse::<Token![,]>()?;
+                    if !content.is_empty() {
+                        _ = content.parse::<LitStr>()?;
+                    }
+                }
+                let value = std::env::var(var_name.value()).map_err(|_| {
+                    syn::Error::new(
+                        var_name.span(),
+                        format!("Environment variable `{}` is not defined", var_name.value()),
+                    )
+                })?;
+                env_vars.push(var_name.value());
+                value
+            }
+            "concat" => Punctuated::<SchemaPath, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .map(|part| {
+                    env_vars.extend(part.env_vars);
+                    part.path.value()
+                })
+                .collect(),
+            _ => return Err(not_a_path(macro_name.span())),
+        };
+        Ok(Self {
+            path: LitStr::new(&value, macro_name.span()),
+            env_vars,
+        })
+    }
+}
+
+struct SchemaFile {
+    full_path: PathBuf,
+    // Span of the path argument this file comes from
+    span: Span,
+    // Set when the path argument is a directory or a glob pattern, as it does not name the file
+    display_path: Option<String>,
+    // Line of the concatenated schema where this file starts, 1-based like `Pos`
+    first_line: usize,
+}
+
+// The schema files, concatenated into a single GraphQL document
+pub(super) struct SchemaFiles {
+    files: Vec<SchemaFile>,
+    // Environment variables the paths of the files depend on
+    env_vars: Vec<String>,
+    content: String,
+}
+impl SchemaFiles {
+    pub(super) fn load(schema_paths: &SchemaPaths) -> syn::Result<Self> {
+        // Relative paths are resolved against the directory of the crate, then against the
+        // current directory, which is the root of the workspace when in a workspace context
+        let mut base_dirs = vec![];
+        if let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") {
+            base_dirs.push(PathBuf::from(manifest_dir));
+        }
+        base_dirs.push(std::env::current_dir().map_err(|e| {
+            syn::Error::new(
+                schema_paths.span(),
+                format!("Could not get current directory: {e}"),
+            )
+        })?);
+        let mut files = vec![];
+        let mut env_vars = schema_paths.env_vars.clone();
+        let mut content = String::new();
+        let mut errors = vec![];
+        for schema_path in schema_paths.paths.iter() {
+            let paths = match expand_env_vars(&schema_path.value(), &mut env_vars)
+                .and_then(|path_value| resolve_path(&base_dirs, &path_value))
+            {
+                Ok(paths) => paths,
+                Err(e) => {
+                    errors.push(syn::Error::new(schema_path.span(), e));
+                    continue;
+                }
+            };
+            for (full_path, display_path) in paths {
+                let schema_str = match std::fs::read_to_string(&full_path) {
+                    Ok(schema_str) => schema_str,
+                    Err(e) => {
+                        errors.push(syn::Error::new(
+                            schema_path.span(),
+                            format!(
+                                "Could not open GraphQL schema file at '{}' ({e})",
+                                full_path.display()
+                            ),
+                        ));
+                        continue;
+                    }
+                };
+                // Parsing each file on its own gives syntax errors located in that file
+                if let Err(e) = graphql_parser::parse_schema::<String>(&schema_str) {
+                    let file = display_path
+                        .as_ref()
+                        .map(|p| format!(" '{p}'"))
+                        .unwrap_or_default();
+                    errors.push(syn::Error::new(
+                        schema_path.span(),
+                        format!("Could not parse GraphQL schema file{file} ({e})"),
+                    ));
+                    continue;
+                }
+                files.push(SchemaFile {
+                    full_path,
+                    span: schema_path.span(),
+                    display_path,
+                    first_line: content.lines().count() + 1,
+                });
+                content.push_str(&schema_str);
+                if !content.ends_with('\n') {
+                    content.push('\n');
+                }
+            }
+        }
+        if let Some(error) = errors.into_iter().reduce(|mut acc, e| {
+            acc.combine(e);
+            acc
+        }) {
+            return Err(error);
+        }
+        env_vars.sort();
+        env_vars.dedup();
+        Ok(Self {
+            files,
+            env_vars,
+            content,
+        })
+    }
+    pub(super) fn content(&self) -> &str {
+        &self.content
+    }
+    // Locates a position of the concatenated document in the file it comes from
+    pub(super) fn locate(&self, pos: Pos) -> (Span, String) {
+        let file = self
+            .files
+            .iter()
+            .rev()
+            .find(|f| f.first_line <= pos.line)
+            .expect("positions are in the document");
+        let line = pos.line - file.first_line + 1;
+        let location = if let Some(ref display_path) = file.display_path {
+            format!("{display_path}, line {line}, column {}", pos.column)
+        } else {
+            format!("line {line}, column {}", pos.column)
+        };
+        (file.span, location)
+    }
+}
+
+// Including the schema files in the generated code makes them inputs of the compilation,
+// so that editing one of them always rebuilds the crate. Reading the environment variables of
+// their paths with `env!` does the same when one of them changes.
+impl ToTokens for SchemaFiles {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let full_paths = self
+            .files
+            .iter()
+            .map(|f| f.full_path.to_string_lossy().into_owned());
+        let env_vars = self.env_vars.iter();
+        tokens.extend(quote! {
+            #(const _: &[u8] = ::core::include_bytes!(#full_paths);)*
+            #(const _: &str = ::core::env!(#env_vars);)*
+        });
+    }
+}
+
+// Replaces the `$VAR` and `${VAR}` environment variables of a path by their value, adding
+// their names to `env_vars`
+fn expand_env_vars(path_value: &str, env_vars: &mut Vec<String>) -> Result<String, String> {
+    let mut expanded = String::with_capacity(path_value.len());
+    let mut rest = path_value;
+    while let Some(index) = rest.find('$') {
+        expanded.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+        let (var_name, remaining) = if let Some(braced) = rest.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| format!("Unclosed `${{` in path '{path_value}'"))?;
+            (&braced[..end], &braced[end + 1..])
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+        if var_name.is_empty() {
+            // Not a variable
+            expanded.push('$');
+            continue;
+        }
+        let value = std::env::var(var_name)
+            .map_err(|_| format!("Environment variable `{var_name}` is not defined"))?;
+        expanded.push_str(&value);
+        env_vars.push(var_name.to_owned());
+        rest = remaining;
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+// Returns the schema files of a path argument, with the path to display in messages
+// when the argument does not name the file. A relative path is resolved against the
+// first base directory where it exists.
+fn resolve_path(
+    base_dirs: &[PathBuf],
+    path_value: &str,
+) -> Result<Vec<(PathBuf, Option<String>)>, String> {
+    let is_pattern = path_value.contains(['*', '?', '[']);
+    let mut resolved = None;
+    for base_dir in base_dirs {
+        let full_path = base_dir.join(path_value);
+        let mut matches = vec![];
+        if is_pattern {
+            matches = glob::glob(&full_path.to_string_lossy())
+                .map_err(|e| format!("Invalid glob pattern '{path_value}' ({e})"))?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Could not read '{path_value}' ({e})"))?;
+            matches.retain(|path| path.is_file());
+        }
+        let exists = if is_pattern {
+            !matches.is_empty()
+        } else {
+            full_path.exists()
+        };
+        // Errors are reported against the first base directory
+        if exists || resolved.is_none() {
+            resolved = Some((base_dir, full_path, matches));
+        }
+        if exists {
+            break;
+        }
+    }
+    let (base_dir, full_path, matches) = resolved.expect("there is always the current directory");
+    let mut paths = if is_pattern {
+        if matches.is_empty() {
+            return Err(format!(
+                "No GraphQL schema file matches the pattern '{path_value}'"
+            ));
+        }
+        matches
+    } else if full_path.is_dir() {
+        let paths = std::fs::read_dir(&full_path)
+            .map_err(|e| {
+                format!(
+                    "Could not read GraphQL schema directory at '{}' ({e})",
+                    full_path.display()
+                )
+            })?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|ext| ext == "graphql" || ext == "gql")
+            })
+            .collect::<Vec<_>>();
+        if paths.is_empty() {
+            return Err(format!(
+                "No `.graphql` or `.gql` file in the GraphQL schema directory at '{}'",
+                full_path.display()
+            ));
+        }
+        paths
+    } else {
+        return Ok(vec![(full_path, None)]);
+    };
+    // Sorted for a deterministic concatenation order
+    paths.sort();
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let display_path = path
+                .strip_prefix(base_dir)
+                .unwrap_or(&path)
+                .display()
+                .to_string();
+            (path, Some(display_path))
+        })
+        .collect())
+}
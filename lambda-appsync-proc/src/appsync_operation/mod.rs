@@ -1,13 +1,18 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, quote_spanned, ToTokens};
-use syn::{braced, parenthesized, parse::Parse, parse_macro_input, Ident, Token, Type, Visibility};
+use syn::{
+    braced, ext::IdentExt, parenthesized, parse::Parse, parse_macro_input, spanned::Spanned, Ident,
+    Token, Type, Visibility,
+};
 
 use crate::common::{Name, OperationKind};
 
 enum ArgsOption {
     KeepOriginalFunctionName,
     WithAppsyncEvent,
+    WithStash,
+    WithPrevResult,
 }
 impl Parse for ArgsOption {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -15,6 +20,8 @@ impl Parse for ArgsOption {
         match ident.to_string().as_str() {
             "keep_original_function_name" => Ok(Self::KeepOriginalFunctionName),
             "with_appsync_event" => Ok(Self::WithAppsyncEvent),
+            "with_stash" => Ok(Self::WithStash),
+            "with_prev_result" => Ok(Self::WithPrevResult),
             _ => Err(syn::Error::new(
                 ident.span(),
                 format!("Unknown option `{ident}`",),
@@ -23,39 +30,59 @@ impl Parse for ArgsOption {
     }
 }
 
+enum OperationTarget {
+    // `query(op)`, `mutation(op)` or `subscription(op)`
+    Root(OperationKind, Name),
+    // `field(Type.field)`, a nested field resolver
+    Field(Name, Name),
+}
+
 struct Args {
-    op_kind: OperationKind,
-    op_name: Name,
+    target: OperationTarget,
     keep_original_function_name: bool,
     with_appsync_event: bool,
+    with_stash: bool,
+    with_prev_result: bool,
 }
 impl Parse for Args {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let op_kind = input.parse::<Ident>()?;
         let op_kind_s = op_kind.to_string();
         let op_kind = match op_kind_s.as_str() {
-            "query" => OperationKind::Query,
-            "mutation" => OperationKind::Mutation,
-            "subscription" => OperationKind::Subscription,
+            "query" => Some(OperationKind::Query),
+            "mutation" => Some(OperationKind::Mutation),
+            "subscription" => Some(OperationKind::Subscription),
+            "field" => None,
             _ => {
                 return Err(syn::Error::new(
                     op_kind.span(),
                     format!(
-                        "Expected one of `query`, `mutation` or `subscription`, got `{op_kind_s}`."
+                        "Expected one of `query`, `mutation`, `subscription` or `field`, got `{op_kind_s}`."
                     ),
                 ));
             }
         };
         let op_name;
         _ = parenthesized!(op_name in input);
-        let op_name = op_name.parse::<Ident>()?;
-        let op_name = Name::from((op_name.to_string(), op_name.span()));
+        let target = if let Some(op_kind) = op_kind {
+            let op_name = op_name.parse::<Ident>()?;
+            OperationTarget::Root(op_kind, Name::from((op_name.to_string(), op_name.span())))
+        } else {
+            let type_name = op_name.call(Ident::parse_any)?;
+            _ = op_name.parse::<Token![.]>()?;
+            let field_name = op_name.call(Ident::parse_any)?;
+            OperationTarget::Field(
+                Name::from((type_name.to_string(), type_name.span())),
+                Name::from((field_name.to_string(), field_name.span())),
+            )
+        };
 
         let mut args = Self {
-            op_kind,
-            op_name,
+            target,
             keep_original_function_name: false,
             with_appsync_event: false,
+            with_stash: false,
+            with_prev_result: false,
         };
 
         while input.peek(Token![,]) {
@@ -68,6 +95,8 @@ impl Parse for Args {
             match option {
                 ArgsOption::KeepOriginalFunctionName => args.keep_original_function_name = true,
                 ArgsOption::WithAppsyncEvent => args.with_appsync_event = true,
+                ArgsOption::WithStash => args.with_stash = true,
+                ArgsOption::WithPrevResult => args.with_prev_result = true,
             }
         }
         Ok(args)
@@ -113,9 +142,8 @@ struct Fct {
     body: TokenStream2,
 }
 impl Fct {
-    fn dummy_function(&self) -> TokenStream2 {
+    fn dummy_function<'a>(&'a self, args: impl Iterator<Item = &'a FctArg>) -> TokenStream2 {
         let fct_name = &self.fct_name;
-        let args = self.args.iter();
         let return_type = &self.return_type;
         quote! {
             #[allow(unused_variables)]
@@ -198,10 +226,59 @@ struct AppsyncOperation {
     fct: Fct,
 }
 impl AppsyncOperation {
+    // The parameters are the operation arguments, then the stash, the previous result and the
+    // event, each of the last three only with its option
+    fn pipeline_args_range(&self) -> std::ops::Range<usize> {
+        let count = self.args.with_stash as usize + self.args.with_prev_result as usize;
+        let end = self
+            .fct
+            .args
+            .len()
+            .saturating_sub(self.args.with_appsync_event as usize);
+        end.saturating_sub(count)..end
+    }
+    // The parameters checked against the operation signature
+    fn signature_args(&self) -> impl Iterator<Item = &FctArg> {
+        let pipeline_args_range = self.pipeline_args_range();
+        self.fct
+            .args
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| !pipeline_args_range.contains(i))
+            .map(|(_, arg)| arg)
+    }
+    // Deserializes the stash and previous result parameters from the event
+    fn pipeline_args_to_tokens(&self) -> TokenStream2 {
+        let mut pipeline_args = self.fct.args[self.pipeline_args_range()].iter();
+        let mut tokens = TokenStream2::new();
+        if self.args.with_stash {
+            let FctArg { name, ty, .. } = pipeline_args.next().expect("checked in try_from");
+            tokens.extend(quote_spanned! {ty.span()=>
+                let #name: #ty = event.stash()?;
+            });
+        }
+        if self.args.with_prev_result {
+            let FctArg { name, ty, .. } = pipeline_args.next().expect("checked in try_from");
+            tokens.extend(quote_spanned! {ty.span()=>
+                let #name: #ty = event.prev_result()?;
+            });
+        }
+        tokens
+    }
     fn op_module_path(&self) -> TokenStream2 {
-        let op_module_name = self.args.op_name.to_var_ident();
+        let (op_type_module, op_module_name) = match self.args.target {
+            OperationTarget::Root(op_kind, ref op_name) => {
+                let op_module_name = op_name.to_var_ident();
+                let op_type_module = Ident::new(op_kind.module_name(), op_module_name.span());
+                (quote! {#op_type_module}, op_module_name)
+            }
+            OperationTarget::Field(ref type_name, ref field_name) => {
+                let op_module_name = field_name.to_var_ident();
+                let type_module_name = type_name.to_var_ident();
+                (quote! {fields::#type_module_name}, op_module_name)
+            }
+        };
         let span = op_module_name.span();
-        let op_type_module = Ident::new(self.args.op_kind.module_name(), span);
         let op_submodule_name = if self.args.with_appsync_event {
             Ident::new("with_event", span)
         } else {
@@ -215,7 +292,7 @@ impl AppsyncOperation {
         let op_module_path = self.op_module_path();
 
         let fct_name = &self.fct.fct_name;
-        let dymmy_fct = self.fct.dummy_function();
+        let dymmy_fct = self.fct.dummy_function(self.signature_args());
         quote! {
             const _: fn() = || {
                 // Compile-time assertion only – never calls the user fn.
@@ -246,17 +323,29 @@ impl AppsyncOperation {
 
         let op_module_path = self.op_module_path();
 
-        let op_fct_name = self
-            .args
-            .op_name
-            .to_prefixed_fct_ident(self.args.op_kind.fct_prefix());
-        let arg_names = self.fct.args.iter().map(|a| &a.name);
+        let op_fct_name = match self.args.target {
+            OperationTarget::Root(op_kind, ref op_name) => {
+                op_name.to_prefixed_fct_ident(op_kind.fct_prefix())
+            }
+            OperationTarget::Field(ref type_name, ref field_name) => {
+                field_name.to_field_fct_ident(type_name)
+            }
+        };
+        let arg_names = self.signature_args().map(|a| &a.name);
+        let pipeline_args = self.pipeline_args_to_tokens();
         let return_type = &self.fct.return_type;
+        // The handlers return their data, or a `PartialData`, with any error convertible into
+        // the `AppsyncError` the operations return
+        let operation_body = quote! {
+            let result: #return_type = async { #operation_body }.await;
+            result.map_err(::core::convert::Into::into)
+        };
         quote! {
             impl crate::Operation {
                 #vis async fn #op_fct_name(
                     mut event: ::lambda_appsync::AppsyncEvent<Self>
-                ) -> #return_type {
+                ) -> ::core::result::Result<impl ::lambda_appsync::IntoPartialData, ::lambda_appsync::AppsyncError> {
+                    #pipeline_args
                     let (#(#arg_names,)*) = #op_module_path::operation_arguments(&mut event)?;
                     #operation_body
                 }
@@ -268,6 +357,28 @@ impl TryFrom<(Args, Fct)> for AppsyncOperation {
     type Error = syn::Error;
 
     fn try_from((args, fct): (Args, Fct)) -> Result<Self, Self::Error> {
+        let required_args = [
+            (args.with_stash, "the stash"),
+            (args.with_prev_result, "the previous result"),
+            (args.with_appsync_event, "the AppSync event"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, arg)| enabled.then_some(arg))
+        .collect::<Vec<_>>();
+        // A missing event parameter alone is reported by the signature check
+        if (args.with_stash || args.with_prev_result) && fct.args.len() < required_args.len() {
+            let expected = match required_args.split_last() {
+                Some((last, [])) => last.to_string(),
+                Some((last, others)) => format!("{} and {last}", others.join(", ")),
+                None => unreachable!("the stash or the previous result is required"),
+            };
+            return Err(syn::Error::new(
+                fct.fct_name.span(),
+                format!(
+                    "Expected the parameters in this order: the operation arguments, then {expected}"
+                ),
+            ));
+        }
         Ok(Self { args, fct })
     }
 }
@@ -243,6 +243,18 @@ impl Name {
             self.span,
         )
     }
+    /// Name of the function resolving the field `self` of the `parent_type`,
+    /// e.g. `field_player_stats` for `Player.stats`
+    pub(crate) fn to_field_fct_ident(&self, parent_type: &Name) -> proc_macro2::Ident {
+        proc_macro2::Ident::new(
+            &format!(
+                "field_{}_{}",
+                parent_type.to_case(CaseType::Snake),
+                self.to_case(CaseType::Snake)
+            ),
+            self.span,
+        )
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
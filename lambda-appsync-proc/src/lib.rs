@@ -1,5 +1,6 @@
 //! Crate not intended for direct use.
 
+mod appsync_error;
 mod appsync_lambda_main;
 mod appsync_operation;
 mod common;
@@ -11,7 +12,7 @@ use proc_macro::TokenStream;
 /// This macro takes a path to a GraphQL schema file and generates the complete foundation
 /// for implementing an AWS AppSync Direct Lambda resolver:
 ///
-/// - Rust types for all GraphQL types (enums, inputs, objects)
+/// - Rust types for all GraphQL types (enums, inputs, objects, unions, interfaces)
 /// - Query/Mutation/Subscription operation enums
 /// - AWS Lambda runtime setup with logging to handle the AWS AppSync event
 /// - Optional AWS SDK client initialization
@@ -23,12 +24,48 @@ use proc_macro::TokenStream;
 ///
 /// - An absolute filesystem path (e.g. "/home/user/project/schema.graphql")
 /// - A relative path, that will be relative to your crate's root directory (e.g. "schema.graphql", "graphql/schema.gql")
-/// - When in a workspace context, the relative path will be relative to the workspace root directory
+/// - When in a workspace context, a relative path not found in your crate's root directory will be
+///   relative to the workspace root directory
+///
+/// Environment variables can be used with `$VAR` or `${VAR}` in the path, or with the `env!` and
+/// `concat!` macros, which is handy to reference a schema shared by several crates:
+///
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// concat!(env!("CARGO_MANIFEST_DIR"), "/../schema.graphql"),
+/// # only_appsync_types = true,
+/// # );
+/// # fn main() {}
+/// ```
+///
+/// Changing one of these environment variables rebuilds the crate with the new schema path.
+///
+/// A schema split across several files can be given as a list of paths, each of them being a file,
+/// a directory (all its `.graphql` and `.gql` files) or a glob pattern (e.g. "graphql/*.graphql").
+/// The files are concatenated into a single schema, in the order of the list and in alphabetical
+/// order for directories and patterns, and errors are located in the file they come from:
+///
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// [
+///     "lambda-appsync-proc/tests/schemas/split/base.graphql",
+///     "lambda-appsync-proc/tests/schemas/split/players.graphql",
+///     "lambda-appsync-proc/tests/schemas/split/games.graphql",
+/// ],
+/// # only_appsync_types = true,
+/// # );
+/// # fn main() {}
+/// ```
+///
+/// The schema files are inputs of the compilation: editing one of them rebuilds the crate. Adding
+/// a file to a directory or matching a glob pattern is only picked up on the next rebuild.
 ///
 /// # Options
 ///
 /// - `batch = bool`: Enable/disable batch request handling (default: true)
 /// - `hook = fn_name`: Add a custom hook function for request validation/auth
+/// - `after_hook = fn_name`: Add a custom hook function receiving the response of every executed
+///   operation, which can inspect or rewrite it
 /// - `exclude_lambda_handler = bool`: Skip generation of Lambda handler code
 /// - `only_lambda_handler = bool`: Only generate Lambda handler code
 /// - `exclude_appsync_types = bool`: Skip generation of GraphQL type definitions
@@ -37,6 +74,22 @@ use proc_macro::TokenStream;
 /// - `only_appsync_operations = bool`: Only generate operation enums
 /// - `type_override` - see section below for details
 /// - `name_override` - see section below for details
+/// - `scalar_map` - see section below for details
+/// - `field_resolver` - see section below for details
+/// - `warn_deprecated_operations = bool`: Emit a deprecation warning when a deprecated operation
+///   is implemented with `appsync_operation` (default: false)
+/// - `check_auth_directives = bool`: Reject the requests whose identity does not match the
+///   authorization directives of the operation, before calling the `hook` (default: false)
+/// - `nullable_inputs_as_maybe = bool`: Use `lambda_appsync::Maybe<T>` instead of `Option<T>` for
+///   nullable input fields and operation arguments, see section below (default: false)
+/// - `check_responses = bool`: Check the results of the operations against the nullability and list
+///   shape of their GraphQL type before responding, see section below (default: false)
+/// - `error_policy = passthrough | sanitize | sanitize("Type", ...)`: Return the errors of the
+///   operations to the clients as they are, or hide their details except for the listed error
+///   types, see section below (default: passthrough)
+/// - `builders = bool`: Generate a typed builder for every `type` and `input` struct, see section
+///   below (default: false)
+/// - `derive`, `derive_all` and `attr` - see section below for details
 /// - `field_type_override` (Deprecated): Same as `type_override`
 ///
 /// ## Type Overrides
@@ -44,8 +97,12 @@ use proc_macro::TokenStream;
 /// The `type_override` option allows overriding Rust types affected to various schema elements:
 ///
 /// - GraphQL `type` and `input` Field types: `type_override = Type.field: CustomType`
+/// - GraphQL `union` member types: `type_override = Union.Member: CustomType`, where `CustomType`
+///   must be a struct as the members are serialized with a `__typename` tag
+/// - GraphQL `interface` field types: `type_override = Interface.field: CustomType`
 /// - Operation return types (Query/Mutation): `type_override = OpType.operation: CustomType`
 /// - Operation arguments (Query/Mutation/Subscription): `type_override = OpType.operation.arg: CustomType`
+/// - Field resolver arguments: `type_override = Type.field.arg: CustomType`
 ///
 /// These overrides are only for the Rust code and must be compatible for serialization/deserialization purposes,
 /// i.e. you can use `String` for a GraphQL `ID` but you cannot use a `u32` for a GraphQL `Float`.
@@ -54,13 +111,460 @@ use proc_macro::TokenStream;
 ///
 /// The `name_override` option supports renaming various schema elements:
 ///
-/// - Type/input/enum names: `name_override = TypeName: NewTypeName`
-/// - Field names: `name_override = Type.field: new_field_name`
+/// - Type/input/enum/union/interface names: `name_override = TypeName: NewTypeName`
+/// - Field names: `name_override = Type.field: new_field_name` (also works for interface fields)
 /// - Enum variants: `name_override = Enum.VARIANT: NewVariant`
+/// - Union variants: `name_override = Union.Member: NewVariant`
 ///
 /// These overrides are only for the Rust code and will not change serialization/deserialization,
 /// i.e. `serde` will rename to the original GraphQL schema name.
 ///
+/// ## Custom Scalars
+///
+/// Custom scalars declared in the schema (e.g. `scalar Decimal`) have no Rust equivalent and must be
+/// mapped to a Rust type with the `scalar_map` option: `scalar_map = Decimal: my_crate::Decimal`.
+/// The mapping applies everywhere the scalar appears (fields, union members, operation arguments
+/// and return types), except where an explicit `type_override` is set.
+///
+/// The macro reports an error for every custom scalar declared in the schema without a mapping,
+/// and for a scalar mapped twice.
+/// As for overrides, the Rust type must be compatible for serialization/deserialization purposes.
+///
+/// ## Derives and Attributes
+///
+/// The generated types derive `Debug`, `Clone`, `Serialize` and `Deserialize`, plus `Copy`,
+/// `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` for enums. More derives and attributes can
+/// be added with these options, which can be repeated:
+///
+/// - Derives on a type/input/enum/union/interface: `derive = TypeName: PartialEq, Eq, Hash`
+/// - Derives on every generated type: `derive_all = PartialEq`
+/// - Attributes on a type/input/enum/union/interface: `attr = TypeName: #[non_exhaustive]`
+///
+/// Type names are the names of the GraphQL schema. Derives already generated for a type are
+/// skipped, and `derive_all` never adds `Default` to enums, unions and interfaces as they have no
+/// default variant. The Rust types of the fields must implement the derived traits, e.g. `Eq`
+/// cannot be derived on a type with a `Float` field.
+///
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "lambda-appsync-proc/tests/schemas/derives.graphql",
+/// #    only_appsync_types = true,
+/// derive_all = PartialEq,
+/// derive = Player: Eq, Hash,
+/// attr = Player: #[non_exhaustive],
+/// # );
+/// # fn main() {
+/// let game = Game { id: lambda_appsync::ID::new(), title: "Crab Rave".to_owned() };
+/// assert_eq!(SearchResult::from(game.clone()), SearchResult::Game(game));
+/// # }
+/// ```
+///
+/// # Descriptions
+///
+/// GraphQL descriptions (`"..."` and `"""..."""`) are emitted as rustdoc on the generated items:
+/// types, fields, enum values, unions, interfaces and the variants of the operation enums. The
+/// descriptions of operations and of their arguments also document the per-operation modules.
+///
+/// # Deprecations
+///
+/// The GraphQL `@deprecated` directive is mapped to `#[deprecated]` on the generated fields, enum
+/// variants, interface accessors and operation enum variants, with its `reason` as the note.
+/// Using them from your own code triggers the usual `deprecated` lint, while the generated code
+/// itself stays warning-free. With `warn_deprecated_operations = true`, implementing a deprecated
+/// operation with `appsync_operation` also triggers the lint.
+///
+/// # Default Values
+///
+/// The default values of operation arguments are used when an argument is omitted. Like in
+/// GraphQL, an explicit `null` is not replaced by the default value, so a nullable argument with a
+/// default value is still passed to the operation as an `Option<T>`:
+///
+/// ```graphql
+/// type Query {
+///     players(limit: Int = 20, offset: Int! = 0, team: Team = RUST, name: String): [Player!]!
+/// }
+/// ```
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "lambda-appsync-proc/tests/schemas/default_values.graphql",
+/// #    exclude_lambda_handler = true,
+/// # );
+/// # use lambda_appsync::{appsync_operation, AppsyncError};
+/// #[appsync_operation(query(players))]
+/// async fn get_players(
+///     limit: Option<i32>,
+///     offset: i32,
+///     team: Option<Team>,
+///     name: Option<String>,
+/// ) -> Result<Vec<Player>, AppsyncError> {
+///     todo!()
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Input fields with a default value keep their type and get it when they are omitted during
+/// deserialization. Input types whose fields all have a default value or are nullable also
+/// implement `Default`.
+///
+/// Default values are checked against the GraphQL type of their argument or field at compile
+/// time. Those of `ID`, `AWSIPAddress`, `type_override` and `scalar_map` types can only be checked
+/// during the deserialization of the argument or input, which fails with an `InvalidArgs` error
+/// when they do not match. `Default` and the builders leave such fields out, as if they had no
+/// default value.
+///
+/// # Omitted and Null Inputs
+///
+/// A nullable input field or argument is an `Option<T>`, which is `None` both when the value is
+/// omitted and when it is explicitly `null`. Partial updates usually need to tell them apart:
+/// with `nullable_inputs_as_maybe = true`, the nullable fields of the `input` types and the
+/// nullable operation arguments are generated as `lambda_appsync::Maybe<T>` instead, which is
+/// `Undefined` when the value is omitted, `Null` when it is `null` and `Value(T)` otherwise:
+///
+/// ```graphql
+/// type Mutation {
+///     updatePlayer(id: ID!, update: PlayerUpdate!, reason: String): Player!
+/// }
+/// input PlayerUpdate {
+///     name: String
+///     nickname: String
+/// }
+/// ```
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "lambda-appsync-proc/tests/schemas/partial_updates.graphql",
+/// #    exclude_lambda_handler = true,
+/// #    nullable_inputs_as_maybe = true,
+/// # );
+/// # use lambda_appsync::{appsync_operation, AppsyncError, Maybe, ID};
+/// # fn load_player(id: ID) -> Player { todo!() }
+/// #[appsync_operation(mutation(updatePlayer))]
+/// async fn update_player(
+///     id: ID,
+///     update: PlayerUpdate,
+///     reason: Maybe<String>,
+/// ) -> Result<Player, AppsyncError> {
+///     let mut player = load_player(id);
+///     if let Maybe::Value(name) = update.name {
+///         player.name = name;
+///     }
+///     // Left untouched if omitted, cleared if null
+///     update.nickname.update(&mut player.nickname);
+///     Ok(player)
+/// }
+/// # fn main() {}
+/// ```
+///
+/// `Undefined` fields are skipped when serializing an input type, while `Null` fields are
+/// serialized as `null`. Arguments with a default value are never omitted once their default is
+/// applied, so they keep being passed as `Option<T>`.
+///
+/// # Builders
+///
+/// With `builders = true`, every `type` and `input` struct gets a `<Struct>::builder()` function
+/// returning a `<Struct>Builder` with a setter per field, taking any value convertible into the
+/// field type. Nullable fields default to `None` (or `Maybe::Undefined`) and fields with a default
+/// value to that value, while non-null fields must be set: `build` does not compile otherwise.
+///
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "lambda-appsync-proc/tests/schemas/builders.graphql",
+/// #    only_appsync_types = true,
+/// #    builders = true,
+/// # );
+/// # fn main() {
+/// // type Player { id: ID! name: String! nickname: String team: Team tags: [String!]! ... }
+/// let player = Player::builder()
+///     .id(lambda_appsync::ID::new())
+///     .name("Ferris")
+///     .tags(vec![])
+///     .team(Team::Rust)
+///     .build();
+/// assert!(player.nickname.is_none());
+/// # }
+/// ```
+///
+/// # Recursive Types
+///
+/// A type containing itself, directly or through other types, would have an infinite size: the
+/// fields of such a cycle are boxed, unless the cycle goes through a list. Each boxed field gets an
+/// accessor of the same name borrowing its value:
+///
+/// ```graphql
+/// type Comment {
+///     text: String!
+///     parent: Comment
+///     replies: [Comment!]!
+/// }
+/// ```
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "lambda-appsync-proc/tests/schemas/recursive_types.graphql",
+/// #     only_appsync_types = true,
+/// # );
+/// // `Comment::parent` is an `Option<Box<Comment>>`
+/// fn root_text(comment: &Comment) -> &str {
+///     match comment.parent() {
+///         Some(parent) => root_text(parent),
+///         None => &comment.text,
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// A field of an interface boxed in one of its implementors is boxed in the interface trait and
+/// in all the other implementors. The accessor of a boxed field returns the same `&T` or
+/// `Option<&T>` as the interface trait accessor of that field, so both can be called alike.
+///
+/// # Response Checks
+///
+/// Type overrides and `serde_json::Value` results can produce a `null` where the schema declares a
+/// non-null type, which AppSync reports as an opaque error. With `check_responses = true`, the
+/// serialized result of every query, mutation and field resolver is checked against its GraphQL
+/// type, including the fields of the returned objects, and a violation is returned as an
+/// `InvalidResponse` error naming its path instead:
+///
+/// ```graphql
+/// type Query {
+///     players: [Player!]!
+/// }
+/// type Player {
+///     name: String!
+/// }
+/// ```
+/// ```text
+/// `players[2].name` is null but its type `String!` is non-null
+/// ```
+///
+/// Fields missing from a returned object are not checked, as they may be resolved by another
+/// resolver. See `lambda_appsync::OutputType` for the details of the checks.
+///
+/// # Error Policy
+///
+/// Errors converted from AWS SDK errors carry their code and message, which can reveal table
+/// names, ARNs or throttling details to the GraphQL clients. With `error_policy = sanitize`, the
+/// error returned by an operation is logged with a new correlation ID and replaced by a generic
+/// `InternalError` error whose `errorInfo` only holds that ID. The error types listed in
+/// `sanitize(...)` are meant for the clients and pass through unchanged:
+///
+/// ```no_run
+/// # mod sub {
+/// lambda_appsync::appsync_lambda_main!(
+///     "schema.graphql",
+///     error_policy = sanitize("NotFound", "ValidationError"),
+/// );
+/// # }
+/// # fn main() {}
+/// ```
+/// ```text
+/// errorType: InternalError
+/// errorMessage: An internal error occurred (correlation ID: 2d4f1a3e-...)
+/// errorInfo: {"correlationId": "2d4f1a3e-..."}
+/// ```
+///
+/// Only the errors of the operations, including the errors of their arguments, are sanitized:
+/// the responses of the `hook` and of `check_auth_directives` are returned as they are. See
+/// `lambda_appsync::AppsyncError::sanitize` for the details.
+///
+/// # Selected Fields
+///
+/// Every `type` struct gets a `<Struct>FieldName` enum with a variant per field, named after its
+/// GraphQL name. The types returned by a query, a mutation or a field resolver also get a
+/// `<Struct>::selected_fields(&event)` function returning the fields selected by the request as a
+/// `lambda_appsync::SelectedFields`, to be called with the events of these operations. Resolvers
+/// can check them without typos to skip loading what the client did not ask for. The types with
+/// fields of another `type` get a `<Struct>SelectedFields` trait, implemented by their
+/// `SelectedFields` with an accessor per such field returning the selected fields of its type:
+///
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "lambda-appsync-proc/tests/schemas/field_resolvers.graphql",
+/// #    exclude_lambda_handler = true,
+/// # );
+/// # use lambda_appsync::{AppsyncEvent, SelectedFields};
+/// // type Player { id: ID! name: String! stats(season: Int): PlayerStats ... }
+/// fn needs_goals(event: &AppsyncEvent<Operation>) -> bool {
+///     let fields: SelectedFields<PlayerFieldName> = Player::selected_fields(event);
+///     // From the `PlayerSelectedFields` trait
+///     let stats_fields: SelectedFields<PlayerStatsFieldName> = fields.stats();
+///     stats_fields.is_selected(PlayerStatsFieldName::Goals)
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The untyped `lambda_appsync::SelectionSet` of a request is returned by
+/// `event.info.selection_set()`.
+///
+/// # Authorization Directives
+///
+/// The AppSync authorization directives of the schema are exposed by the `allowed_auth_modes()` and
+/// `required_cognito_groups()` methods of the generated `Operation` enum. The directives of a field
+/// replace those of its type, and an operation without any directive returns empty slices, meaning
+/// the default authorization mode of the API applies:
+///
+/// | Directive                                       | `AppsyncAuthMode`  |
+/// |-------------------------------------------------|--------------------|
+/// | `@aws_api_key`                                  | `ApiKey`           |
+/// | `@aws_iam`                                      | `Iam`              |
+/// | `@aws_oidc`                                     | `Oidc`             |
+/// | `@aws_lambda`                                   | `Lambda`           |
+/// | `@aws_cognito_user_pools(cognito_groups: [..])` | `CognitoUserPools` |
+/// | `@aws_auth(cognito_groups: [..])`               | `CognitoUserPools` |
+///
+/// ```graphql
+/// type Mutation {
+///     deletePlayer(id: ID!): Player!
+///         @aws_cognito_user_pools(cognito_groups: ["admin", "moderator"])
+///         @aws_iam
+/// }
+/// ```
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "lambda-appsync-proc/tests/schemas/auth_directives.graphql",
+/// #    exclude_lambda_handler = true,
+/// # );
+/// use lambda_appsync::AppsyncAuthMode;
+///
+/// # fn main() {
+/// let op = Operation::Mutation(MutationField::DeletePlayer);
+/// assert_eq!(
+///     op.allowed_auth_modes(),
+///     &[AppsyncAuthMode::CognitoUserPools, AppsyncAuthMode::Iam]
+/// );
+/// assert_eq!(op.required_cognito_groups(), &["admin", "moderator"]);
+/// # }
+/// ```
+///
+/// With `check_auth_directives = true`, the Lambda handler checks the identity of each request
+/// with `AppsyncIdentity::check_auth` and answers with an `Unauthorized` error when its
+/// authorization mode is not allowed, or when a Cognito user is not in one of the required groups.
+///
+/// # Subscriptions
+///
+/// The `@aws_subscribe(mutations: [...])` directives of the subscriptions are checked: each
+/// mutation must exist and return the same type as the subscription, regardless of nullability,
+/// as AppSync sends the mutation result to the subscribers. The generated `MutationField` enum
+/// gets a `subscriptions()` method returning the subscriptions triggered by each mutation:
+///
+/// ```graphql
+/// type Subscription {
+///     onCreatePlayer: Player @aws_subscribe(mutations: ["createPlayer"])
+///     onPlayerChange(id: ID): Player
+///         @aws_subscribe(mutations: ["createPlayer", "updatePlayer", "deletePlayer"])
+/// }
+/// ```
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "lambda-appsync-proc/tests/schemas/subscriptions.graphql",
+/// #    exclude_lambda_handler = true,
+/// # );
+/// # fn main() {
+/// assert!(matches!(
+///     MutationField::CreatePlayer.subscriptions(),
+///     [SubscriptionField::OnCreatePlayer, SubscriptionField::OnPlayerChange]
+/// ));
+/// # }
+/// ```
+///
+/// # Type Extensions
+///
+/// Type extensions (`extend type`, `extend input`, `extend enum`, `extend union` and
+/// `extend interface`) are merged into their base definition before any code is generated,
+/// which makes it possible to split the operations of a schema per domain with
+/// `extend type Query { ... }`. Extending a type that does not exist is an error, as is an
+/// extension redefining a field, enum value or union member.
+///
+/// # Unions
+///
+/// Each GraphQL `union` becomes a Rust enum with one tuple variant per member type. The enum
+/// is (de)serialized with a `__typename` field holding the GraphQL name of the concrete type,
+/// which is what AppSync expects to resolve the union. A `From` implementation is also
+/// generated for each member type:
+///
+/// ```graphql
+/// union Staff = Coach | Referee
+/// ```
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     only_appsync_types = true,
+/// # );
+/// # fn main() {
+/// let staff: Staff = Coach {
+///     id: lambda_appsync::ID::new(),
+///     name: "John Doe".to_owned(),
+///     team: Team::Rust,
+/// }
+/// .into();
+/// // Serialized as {"__typename": "Coach", "id": "...", "name": "John Doe", "team": "RUST"}
+/// # }
+/// ```
+///
+/// # Interfaces
+///
+/// Each GraphQL `interface` generates two items:
+///
+/// - A trait named after the interface with a `Fields` suffix, providing an accessor for every
+///   field of the interface, borrowing it as `&T`, or as `Option<&T>` when it is nullable. It is
+///   implemented by all the structs of the implementing types, which may declare a nullable field
+///   of the interface as non-null. The macro reports an error when a field of an implementing type
+///   has another type.
+/// - An enum over every implementing type, (de)serialized with a `__typename` field like unions,
+///   that also implements the trait.
+///
+/// ```graphql
+/// interface Person {
+///   id: ID!
+///   name: String!
+/// }
+/// type Coach implements Person { id: ID!, name: String!, team: Team! }
+/// type Referee implements Person { id: ID!, name: String! }
+/// ```
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     only_appsync_types = true,
+/// # );
+/// # fn main() {
+/// let person: Person = Referee {
+///     id: lambda_appsync::ID::new(),
+///     name: "John Doe".to_owned(),
+/// }
+/// .into();
+/// // Accessors from the `PersonFields` trait
+/// assert_eq!(person.name(), "John Doe");
+/// # }
+/// ```
+///
+/// # Field Resolvers
+///
+/// By default, only the fields of the `Query`, `Mutation` and `Subscription` types are dispatched
+/// to operation handlers. Fields of other types resolved by the Lambda (e.g. `Player.team`) must be
+/// declared with `field_resolver = Type.field`, and are then implemented with
+/// `#[appsync_operation(field(Type.field))]`.
+///
+/// Each declared field adds a variant to the `Operation` enum, named after the Rust type of its parent
+/// (e.g. `Operation::Player(PlayerField::Team)`). The parent is deserialized from the `source` of
+/// the [AppsyncEvent](struct.AppsyncEvent.html) and given to the handler before the field arguments:
+///
+/// ```no_run
+/// lambda_appsync::appsync_lambda_main!(
+///     "schema.graphql",
+/// #   exclude_lambda_handler = true,
+///     field_resolver = Player.team,
+/// );
+///
+/// #[lambda_appsync::appsync_operation(field(Player.team))]
+/// async fn player_team(player: Player) -> Result<Team, lambda_appsync::AppsyncError> {
+///     // `player.team` is `None`, the field is being resolved
+///     todo!("find the team of player {}", player.id)
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The `source` does not contain the fields resolved by the Lambda, so the non-null ones are
+/// `Option`s in the parent struct (e.g. `team: Option<Team>` for `team: Team!`). The other
+/// operations returning the parent type can leave them `None`.
+///
 /// # AWS SDK Clients
 ///
 /// AWS SDK clients can be initialized by providing function definitions that return a cached SDK client type.
@@ -147,6 +651,41 @@ use proc_macro::TokenStream;
 /// # fn main() {}
 /// ```
 ///
+/// ## Inspecting or rewriting the responses with a post-execution hook:
+/// ```no_run
+/// # mod sub {
+/// use lambda_appsync::{appsync_lambda_main, AppsyncIdentity, AppsyncResponse};
+///
+/// fn is_admin(identity: &AppsyncIdentity) -> bool {
+///     todo!()
+/// }
+///
+/// // Called with the response of every executed operation, before it is returned to AppSync.
+/// // The responses of the `hook` are not passed to it. With `error_policy = sanitize`, it receives
+/// // the errors before they are sanitized.
+/// async fn after_hook(
+///     operation: &Operation,
+///     identity: &AppsyncIdentity,
+///     mut response: AppsyncResponse,
+/// ) -> AppsyncResponse {
+///     lambda_appsync::log::info!("operation={operation:?} error={:?}", response.error());
+///     // Only the admins can see the emails
+///     if !is_admin(identity) {
+///         if let Some(data) = response.data_mut().and_then(|data| data.as_object_mut()) {
+///             data.remove("email");
+///         }
+///     }
+///     response
+/// }
+///
+/// appsync_lambda_main!(
+///     "schema.graphql",
+///     after_hook = after_hook,
+/// );
+/// # }
+/// # fn main() {}
+/// ```
+///
 /// ## Generate only types for lib code generation:
 /// ```no_run
 /// # mod sub {
@@ -222,7 +761,7 @@ pub fn appsync_lambda_main(input: TokenStream) -> TokenStream {
 }
 
 /// Marks an async function as an AWS AppSync resolver operation, binding it to a specific Query,
-/// Mutation or Subscription operation defined in the GraphQL schema.
+/// Mutation or Subscription operation defined in the GraphQL schema, or to a field of another type.
 ///
 /// The marked function must match the signature of the GraphQL operation, with parameters and return
 /// type matching what is defined in the schema. The function will be wired up to handle requests
@@ -304,7 +843,127 @@ pub fn appsync_lambda_main(input: TokenStream) -> TokenStream {
 ///
 /// Note that the `args` field of the [AppsyncEvent](struct.AppsyncEvent.html) will always contain
 /// [Null](https://docs.rs/serde_json/latest/serde_json/enum.Value.html#variant.Null) at this stage because its initial content is taken to extract
-/// the argument values for the operation.
+/// the argument values for the operation. Likewise, the `source` field is taken to extract the parent
+/// value of field resolvers.
+///
+/// ## Using the stash and previous result of a pipeline resolver
+///
+/// When the lambda is a function of an AppSync pipeline resolver, the `with_stash` and
+/// `with_prev_result` flags deserialize the pipeline `stash` and the result of the previous
+/// function into typed parameters. The parameters are in this order: the operation arguments,
+/// the stash, the previous result, then the [AppsyncEvent](struct.AppsyncEvent.html) reference
+/// if `with_appsync_event` is also used.
+/// A value that does not deserialize into the parameter type is reported as an `InvalidStash`
+/// or `InvalidPrevResult` error:
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// # async fn dynamodb_create_player(name: String, team: String) -> Result<Player, AppsyncError> {
+/// #    todo!()
+/// # }
+/// use lambda_appsync::{appsync_operation, AppsyncError};
+/// use serde::Deserialize;
+///
+/// // Your types are declared at the crate level by the appsync_lambda_main! macro
+/// use crate::Player;
+///
+/// #[derive(Deserialize)]
+/// struct Stash {
+///     team: String,
+/// }
+///
+/// #[appsync_operation(mutation(createPlayer), with_stash, with_prev_result)]
+/// async fn create_player(
+///     name: String,
+///     stash: Stash,
+///     prev_result: Option<Player>,
+/// ) -> Result<Player, AppsyncError> {
+///     match prev_result {
+///         // A previous function already created the player
+///         Some(player) => Ok(player),
+///         None => Ok(dynamodb_create_player(name, stash.team).await?),
+///     }
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// ## Returning data with an error
+///
+/// Handlers can also return a `lambda_appsync::PartialResult<T>` (an alias of
+/// `Result<PartialData<T>, AppsyncError>`) to send data and an error to AppSync together, the
+/// GraphQL response then contains both, and so does any alias of these types. Error details for
+/// the clients go in the `error_info` of the [AppsyncError](struct.AppsyncError.html):
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// # async fn dynamodb_get_players() -> Result<Vec<Result<Player, String>>, AppsyncError> {
+/// #    todo!()
+/// # }
+/// use lambda_appsync::{appsync_operation, AppsyncError, PartialData, PartialResult};
+///
+/// // Your types are declared at the crate level by the appsync_lambda_main! macro
+/// use crate::Player;
+///
+/// #[appsync_operation(query(players))]
+/// async fn get_players() -> PartialResult<Vec<Player>> {
+///     let (players, failures): (Vec<_>, Vec<_>) = dynamodb_get_players()
+///         .await?
+///         .into_iter()
+///         .partition(Result::is_ok);
+///     let players = players.into_iter().flatten().collect();
+///     if failures.is_empty() {
+///         return Ok(PartialData::new(players));
+///     }
+///     let ids = failures.into_iter().filter_map(Result::err).collect::<Vec<_>>();
+///     Ok(PartialData::new(players).with_error(
+///         AppsyncError::new("PartialFailure", "Some players could not be read")
+///             .with_error_info_entry("ids", ids),
+///     ))
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// ## Returning a custom error type
+///
+/// The error type of the `Result` can be any type convertible into an
+/// [AppsyncError](struct.AppsyncError.html), typically an error enum deriving
+/// [AppsyncError](derive.AppsyncError.html), including through an alias like `PlayerResult<T>`
+/// below. The error is converted when the handler returns it:
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, AppsyncError, ID};
+///
+/// // Your types are declared at the crate level by the appsync_lambda_main! macro
+/// use crate::Player;
+///
+/// #[derive(Debug, thiserror::Error, AppsyncError)]
+/// enum PlayerError {
+///     #[error("No player with the ID {0}")]
+///     #[appsync(type = "NotFound")]
+///     UnknownPlayer(ID),
+/// }
+///
+/// type PlayerResult<T> = Result<T, PlayerError>;
+///
+/// #[appsync_operation(mutation(deletePlayer))]
+/// async fn delete_player(id: ID) -> PlayerResult<Player> {
+///     Err(PlayerError::UnknownPlayer(id))
+/// }
+/// # }
+/// # fn main() {}
+/// ```
 ///
 /// ## Preserve original function name
 ///
@@ -337,6 +996,33 @@ pub fn appsync_lambda_main(input: TokenStream) -> TokenStream {
 /// # fn main() {}
 /// ```
 ///
+/// ## Resolving fields of non-root types
+///
+/// Fields declared with the `field_resolver` option of [appsync_lambda_main!] are bound with `field(Type.field)`.
+/// The handler receives the parent value, deserialized from the event `source`, as its first argument,
+/// followed by the field arguments:
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// #     field_resolver = Player.team,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, AppsyncError};
+///
+/// // Your types are declared at the crate level by the appsync_lambda_main! macro
+/// use crate::{Player, Team};
+///
+/// // Resolve the 'team' field of a 'Player'
+/// #[appsync_operation(field(Player.team))]
+/// async fn player_team(player: Player) -> Result<Team, AppsyncError> {
+///     // `player.team` is `None`, the resolved fields are not in the `source`
+///     todo!("find the team of player {}", player.id)
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
 /// ## Using enhanced subscription filters
 ///
 /// ```no_run
@@ -391,3 +1077,52 @@ pub fn appsync_lambda_main(input: TokenStream) -> TokenStream {
 pub fn appsync_operation(args: TokenStream, input: TokenStream) -> TokenStream {
     appsync_operation::appsync_operation_impl(args, input)
 }
+
+/// Derives `lambda_appsync::AppsyncErrorKind` and `From<T> for lambda_appsync::AppsyncError` for a
+/// domain error type, so that operation handlers can return `Result<T, MyError>` directly.
+///
+/// The type must implement [Display](std::fmt::Display), which gives the `errorMessage` of the
+/// GraphQL error, e.g. with `thiserror`. The `errorType` is the name of the variant (or of the
+/// struct), unless overridden with `#[appsync(type = "...")]` on the variant, or on the enum for
+/// all its variants. A field marked with `#[appsync(info)]` is serialized as the `errorInfo`.
+///
+/// # Example
+///
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, AppsyncError, ID};
+/// use crate::Player;
+///
+/// #[derive(Debug, thiserror::Error, AppsyncError)]
+/// enum PlayerError {
+///     // errorType: "NotFound"
+///     #[error("No player with the ID {0}")]
+///     #[appsync(type = "NotFound")]
+///     UnknownPlayer(ID),
+///     // errorType: "InvalidName", errorInfo: the details
+///     #[error("Invalid player name")]
+///     InvalidName {
+///         #[appsync(info)]
+///         details: lambda_appsync::serde_json::Value,
+///     },
+/// }
+///
+/// # async fn dynamodb_get_player(id: ID) -> Option<Player> { todo!() }
+/// #[appsync_operation(query(player))]
+/// async fn get_player(id: ID) -> Result<Option<Player>, PlayerError> {
+///     match dynamodb_get_player(id).await {
+///         Some(player) => Ok(Some(player)),
+///         None => Err(PlayerError::UnknownPlayer(id)),
+///     }
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+#[proc_macro_derive(AppsyncError, attributes(appsync))]
+pub fn appsync_error(input: TokenStream) -> TokenStream {
+    appsync_error::appsync_error_impl(input)
+}
@@ -0,0 +1,23 @@
+#![deny(deprecated)]
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/deprecations.graphql",
+    exclude_lambda_handler = true,
+    warn_deprecated_operations = true,
+);
+
+fn main() {}
+
+// Not deprecated
+#[appsync_operation(query(player))]
+async fn get_player(_id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(None)
+}
+
+// Deprecated with a reason
+#[appsync_operation(query(players))]
+async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+    Ok(vec![])
+}
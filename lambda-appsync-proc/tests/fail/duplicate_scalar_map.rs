@@ -0,0 +1,12 @@
+use lambda_appsync::appsync_lambda_main;
+
+// Decimal is mapped twice
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/custom_scalars.graphql",
+    exclude_lambda_handler = true,
+    scalar_map = Decimal: String,
+    scalar_map = Color: String,
+    scalar_map = Decimal: f64,
+);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use lambda_appsync::appsync_lambda_main;
+
+// PlayerStats is renamed like the `PlayerField` enum of the `Player` field resolvers
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/field_resolvers.graphql",
+    exclude_lambda_handler = true,
+    field_resolver = Player.stats,
+    name_override = PlayerStats: PlayerField,
+);
+
+fn main() {}
@@ -0,0 +1,10 @@
+mod no_run {
+    use lambda_appsync::{appsync_lambda_main, AppsyncResponse};
+    // The response is missing
+    async fn audit(_operation: &Operation) -> AppsyncResponse {
+        todo!()
+    }
+    appsync_lambda_main!("../../../../schema.graphql", after_hook = audit);
+}
+
+fn main() {}
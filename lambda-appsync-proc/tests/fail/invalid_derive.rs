@@ -0,0 +1,10 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/derives.graphql",
+    exclude_lambda_handler = true,
+    derive = Player: PartialEq,
+    derive = Unknown: PartialEq,
+);
+
+fn main() {}
@@ -0,0 +1,24 @@
+use lambda_appsync::AppsyncError;
+
+#[derive(Debug, thiserror::Error, AppsyncError)]
+enum PlayerError {
+    #[error("Not found")]
+    #[appsync(kind = "NotFound")]
+    NotFound,
+    #[error("Invalid")]
+    Invalid {
+        #[appsync(info)]
+        field: String,
+        #[appsync(info)]
+        code: String,
+    },
+    #[error("Timeout")]
+    Timeout(#[appsync(type = "Timeout")] u64),
+}
+
+#[derive(AppsyncError)]
+union Raw {
+    code: u32,
+}
+
+fn main() {}
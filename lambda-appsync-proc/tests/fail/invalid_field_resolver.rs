@@ -0,0 +1,12 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/field_resolvers.graphql",
+    exclude_lambda_handler = true,
+    // Unknown field
+    field_resolver = Player.score,
+    // Root types are already operations
+    field_resolver = Query.player,
+);
+
+fn main() {}
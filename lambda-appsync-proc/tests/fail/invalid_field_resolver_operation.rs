@@ -0,0 +1,21 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError};
+
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/field_resolvers.graphql",
+    exclude_lambda_handler = true,
+    field_resolver = Player.stats,
+);
+
+fn main() {}
+
+// `Player.teammates` is not declared with `field_resolver`
+#[appsync_operation(field(Player.teammates))]
+async fn player_teammates(_player: Player, _first: i32) -> Result<Option<Vec<Player>>, AppsyncError> {
+    Ok(None)
+}
+
+// The parent is missing
+#[appsync_operation(field(Player.stats))]
+async fn player_stats(_season: Option<i32>) -> Result<Option<PlayerStats>, AppsyncError> {
+    Ok(None)
+}
@@ -0,0 +1,12 @@
+use lambda_appsync::appsync_lambda_main;
+
+// Post is missing the `createdAt` field of Node and its `related` field has another type,
+// Comment overrides the type of the `id` field of Node and implements an interface that does
+// not exist
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/invalid_interface_impl.graphql",
+    exclude_lambda_handler = true,
+    type_override = Comment.id: String,
+);
+
+fn main() {}
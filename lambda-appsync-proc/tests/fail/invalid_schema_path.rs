@@ -0,0 +1,24 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    [
+        concat!(env!("LAMBDA_APPSYNC_UNDEFINED_VAR"), "/schema.graphql"),
+    ],
+    exclude_lambda_handler = true,
+);
+
+mod env_var {
+    lambda_appsync::appsync_lambda_main!(
+        "${LAMBDA_APPSYNC_UNDEFINED_VAR}/schema.graphql",
+        exclude_lambda_handler = true,
+    );
+}
+
+mod not_a_path {
+    lambda_appsync::appsync_lambda_main!(
+        include_str!("../../../../schema.graphql"),
+        exclude_lambda_handler = true,
+    );
+}
+
+fn main() {}
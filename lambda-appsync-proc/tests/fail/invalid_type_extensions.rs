@@ -0,0 +1,10 @@
+use lambda_appsync::appsync_lambda_main;
+
+// Game does not exist, Player is not an enum, the Player extension redefines `id` and
+// defines `name` twice, and the Team extension redefines `RUST`
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/invalid_type_extensions.graphql",
+    exclude_lambda_handler = true,
+);
+
+fn main() {}
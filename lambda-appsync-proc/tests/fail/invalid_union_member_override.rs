@@ -0,0 +1,11 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    // Player is not a member of the Staff union
+    name_override = Staff.Player: Athlete,
+    type_override = Staff.Player: String,
+);
+
+fn main() {}
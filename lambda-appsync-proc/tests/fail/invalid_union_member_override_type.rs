@@ -0,0 +1,11 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../schema.graphql",
+    exclude_lambda_handler = true,
+    // Union members are serialized with a `__typename` tag, they must stay structs
+    type_override = Staff.Coach: String,
+    type_override = Staff.Referee: Team,
+);
+
+fn main() {}
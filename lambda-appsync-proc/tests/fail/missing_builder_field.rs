@@ -0,0 +1,15 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/builders.graphql",
+    only_appsync_types = true,
+    builders = true,
+);
+
+fn main() {
+    // `tags` is non-null and not set
+    let _player = Player::builder()
+        .id(lambda_appsync::ID::new())
+        .name("Ferris")
+        .build();
+}
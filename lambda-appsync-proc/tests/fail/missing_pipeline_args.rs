@@ -0,0 +1,9 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent};
+
+appsync_lambda_main!("../../../../schema.graphql");
+
+// Missing stash and previous result arguments
+#[appsync_operation(query(players), with_stash, with_prev_result, with_appsync_event)]
+async fn get_players(_event: &AppsyncEvent<Operation>) -> Result<Vec<Player>, AppsyncError> {
+    Ok(vec![])
+}
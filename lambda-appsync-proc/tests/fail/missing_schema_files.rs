@@ -0,0 +1,12 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    [
+        "../../../../lambda-appsync-proc/tests/schemas/split/base.graphql",
+        "../../../../lambda-appsync-proc/tests/schemas/split/*.gql",
+        "../../../../lambda-appsync-proc/tests/schemas/missing.graphql",
+    ],
+    exclude_lambda_handler = true,
+);
+
+fn main() {}
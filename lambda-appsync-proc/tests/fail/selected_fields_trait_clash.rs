@@ -0,0 +1,10 @@
+use lambda_appsync::appsync_lambda_main;
+
+// PlayerStats is renamed like the `PlayerSelectedFields` trait of the `Player` fields
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/field_resolvers.graphql",
+    exclude_lambda_handler = true,
+    name_override = PlayerStats: PlayerSelectedFields,
+);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use lambda_appsync::appsync_lambda_main;
+
+// Color is not mapped and Money is not a scalar of the schema
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/custom_scalars.graphql",
+    exclude_lambda_handler = true,
+    scalar_map = Decimal: String,
+    scalar_map = Money: String,
+);
+
+fn main() {}
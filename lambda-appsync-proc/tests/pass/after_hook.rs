@@ -0,0 +1,22 @@
+mod no_run {
+    use lambda_appsync::{
+        appsync_lambda_main, AppsyncEvent, AppsyncIdentity, AppsyncResponse,
+    };
+    async fn verify_request(_event: &AppsyncEvent<Operation>) -> Option<AppsyncResponse> {
+        None // Allow all requests
+    }
+    async fn audit(
+        _operation: &Operation,
+        _identity: &AppsyncIdentity,
+        response: AppsyncResponse,
+    ) -> AppsyncResponse {
+        response // Keep all responses
+    }
+    appsync_lambda_main!(
+        "../../../../schema.graphql",
+        hook = verify_request,
+        after_hook = audit
+    );
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use lambda_appsync::appsync_lambda_main;
+
+// Recursive object types, unions and interfaces
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/recursive_types.graphql",
+    exclude_lambda_handler = true,
+    check_responses = true,
+);
+
+fn main() {}
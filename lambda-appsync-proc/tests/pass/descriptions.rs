@@ -0,0 +1,30 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+// Descriptions become rustdoc on the generated items
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/descriptions.graphql",
+    exclude_lambda_handler = true,
+);
+
+fn main() {
+    let player = Player {
+        id: ID::new(),
+        name: "John Doe".to_owned(),
+        team: Some(Team::Rust),
+    };
+    let _staff: Staff = player.into();
+}
+
+#[appsync_operation(query(player))]
+async fn get_player(_id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(None)
+}
+
+#[appsync_operation(mutation(createPlayer))]
+async fn create_player(player: PlayerInput) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name: player.name,
+        team: None,
+    })
+}
@@ -0,0 +1,35 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, ID};
+
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/field_resolvers.graphql",
+    exclude_lambda_handler = true,
+    field_resolver = Player.stats,
+    field_resolver = Player.teammates,
+);
+
+fn main() {}
+
+#[appsync_operation(query(player))]
+async fn get_player(_id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(None)
+}
+
+#[appsync_operation(field(Player.stats), keep_original_function_name)]
+async fn player_stats(
+    player: Player,
+    season: Option<i32>,
+) -> Result<Option<PlayerStats>, AppsyncError> {
+    Ok(player.stats.map(|stats| PlayerStats {
+        season: season.unwrap_or(stats.season),
+        goals: stats.goals,
+    }))
+}
+
+#[appsync_operation(field(Player.teammates), with_appsync_event)]
+async fn player_teammates(
+    _player: Player,
+    first: i32,
+    _event: &AppsyncEvent<Operation>,
+) -> Result<Option<Vec<Player>>, AppsyncError> {
+    Ok(Some(Vec::with_capacity(first as usize)))
+}
@@ -0,0 +1,41 @@
+// Test the interface accessors of implementors with non-null versions of the interface fields
+lambda_appsync::appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/interfaces.graphql",
+    only_appsync_types = true,
+);
+
+fn labels(node: &impl NodeFields) -> (Option<&lambda_appsync::ID>, Option<&String>) {
+    (node.id(), node.label())
+}
+
+fn main() {
+    let folder = Folder {
+        id: lambda_appsync::ID::new(),
+        label: None,
+        parent: Some(Box::new(Folder {
+            id: lambda_appsync::ID::new(),
+            label: Some("Root".to_owned()),
+            parent: None,
+        })),
+    };
+    // `parent` is boxed in every implementor since `Folder` is recursive
+    let document = Document {
+        id: lambda_appsync::ID::new(),
+        label: "Readme".to_owned(),
+        parent: Box::new(folder.clone()),
+    };
+
+    assert_eq!(labels(&folder), (Some(&folder.id), None));
+    assert_eq!(labels(&document), (Some(&document.id), Some(&document.label)));
+    assert_eq!(
+        NodeFields::parent(&folder).and_then(|p| p.label.as_deref()),
+        Some("Root")
+    );
+    assert_eq!(
+        NodeFields::parent(&document).map(|p| &p.id),
+        Some(&folder.id)
+    );
+
+    let node = Node::from(document.clone());
+    assert_eq!(node.label(), Some(&document.label));
+}
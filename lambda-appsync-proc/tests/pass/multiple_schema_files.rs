@@ -0,0 +1,60 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+// A directory: all its `.graphql` and `.gql` files are concatenated
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/split",
+    exclude_lambda_handler = true,
+);
+
+// A list of files
+mod listed {
+    lambda_appsync::appsync_lambda_main!(
+        [
+            "../../../../lambda-appsync-proc/tests/schemas/split/base.graphql",
+            "../../../../lambda-appsync-proc/tests/schemas/split/players.graphql",
+            "../../../../lambda-appsync-proc/tests/schemas/split/games.graphql",
+        ],
+        only_appsync_types = true,
+    );
+}
+
+// A glob pattern
+mod globbed {
+    lambda_appsync::appsync_lambda_main!(
+        "../../../../lambda-appsync-proc/tests/schemas/split/*.graphql",
+        only_appsync_types = true,
+    );
+}
+
+fn main() {
+    let _game = Game {
+        id: ID::new(),
+        players: vec![],
+        winner: Some(Team::Rust),
+    };
+    let _game = listed::Game {
+        id: ID::new(),
+        players: vec![],
+        winner: Some(listed::Team::Python),
+    };
+    let _game = globbed::Game {
+        id: ID::new(),
+        players: vec![],
+        winner: Some(globbed::Team::Js),
+    };
+    let _op = Operation::Query(QueryField::Game);
+}
+
+#[appsync_operation(query(player))]
+async fn get_player(_id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(None)
+}
+
+#[appsync_operation(mutation(createPlayer))]
+async fn create_player(name: String, team: Team) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name,
+        team,
+    })
+}
@@ -0,0 +1,39 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+mod money {
+    use lambda_appsync::serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(crate = "lambda_appsync::serde")]
+    pub struct Decimal(pub String);
+}
+
+// Custom scalars are mapped everywhere they appear
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/custom_scalars.graphql",
+    exclude_lambda_handler = true,
+    scalar_map = Decimal: money::Decimal,
+    scalar_map = Color: String,
+    // Explicit type overrides still take precedence
+    type_override = Product.discount: f64,
+);
+
+fn main() {
+    let product = Product {
+        id: ID::new(),
+        price: money::Decimal("9.99".to_owned()),
+        discount: Some(0.1),
+        colors: vec!["red".to_owned()],
+    };
+    assert_eq!(product.price, money::Decimal("9.99".to_owned()));
+}
+
+#[appsync_operation(query(price))]
+async fn price(_id: ID) -> Result<money::Decimal, AppsyncError> {
+    Ok(money::Decimal("9.99".to_owned()))
+}
+
+#[appsync_operation(query(palette))]
+async fn palette(colors: Vec<String>) -> Result<Vec<String>, AppsyncError> {
+    Ok(colors)
+}
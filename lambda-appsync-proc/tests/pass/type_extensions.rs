@@ -0,0 +1,46 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+// Extensions are merged into their base definitions
+appsync_lambda_main!(
+    "../../../../lambda-appsync-proc/tests/schemas/type_extensions.graphql",
+    exclude_lambda_handler = true,
+);
+
+fn main() {
+    // Extended enum
+    assert_eq!(Team::all(), [Team::Rust, Team::Python]);
+
+    // Extended input
+    let _input = PlayerInput {
+        name: "JohnDoe".to_owned(),
+        team: Some(Team::Python),
+    };
+
+    // Extended type, also implementing an interface through an extension
+    let player = Player {
+        id: ID::new(),
+        name: "JohnDoe".to_owned(),
+        team: Team::Rust,
+    };
+    let _node = Node::from(player.clone());
+
+    // Extended union
+    let _staff = [Staff::Player(player), Staff::Coach(Coach { id: ID::new() })];
+
+    // Extended Query
+    let _op = Operation::Query(QueryField::Player);
+}
+
+#[appsync_operation(query(player))]
+async fn get_player(_id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(None)
+}
+
+#[appsync_operation(mutation(createPlayer))]
+async fn create_player(input: PlayerInput) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name: input.name,
+        team: input.team.unwrap_or(Team::Rust),
+    })
+}
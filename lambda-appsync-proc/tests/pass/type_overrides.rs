@@ -23,6 +23,8 @@ appsync_lambda_main!(
     type_override = WeirdFieldNames.i8: String,
     type_override = WeirdFieldNames.i16: String,
     type_override = WeirdFieldNames.u8: String,
+    // Union member override
+    type_override = Staff.Referee: Coach,
     // Return value override
     type_override = Query.gameStatus: String,
     type_override = Mutation.setGameStatus: String,
@@ -33,6 +35,12 @@ appsync_lambda_main!(
 );
 
 fn main() {
+    // Both union variants now hold a Coach
+    let _staff = Staff::Referee(Coach {
+        id: lambda_appsync::ID::new(),
+        name: "JohnDoe".to_string(),
+        team: Team::Rust,
+    });
     let _weird = WeirdFieldNames {
         r#as: "test".into(),
         r#async: "test".into(),
@@ -76,6 +76,10 @@
 
 mod aws_scalars;
 mod id;
+mod maybe;
+mod output_type;
+mod request;
+mod selection_set;
 pub mod subscription_filters;
 
 use std::{collections::HashMap, ops::BitOr};
@@ -94,6 +98,10 @@ pub use aws_scalars::{
     url::AWSUrl,
 };
 pub use id::ID;
+pub use maybe::Maybe;
+pub use output_type::{ObjectType, OutputType};
+pub use request::{AppsyncHeaders, AppsyncRequest};
+pub use selection_set::{SelectableField, SelectedFields, SelectionSet};
 
 #[doc(inline)]
 pub use lambda_appsync_proc::appsync_lambda_main;
@@ -101,6 +109,9 @@ pub use lambda_appsync_proc::appsync_lambda_main;
 #[doc(inline)]
 pub use lambda_appsync_proc::appsync_operation;
 
+#[doc(inline)]
+pub use lambda_appsync_proc::AppsyncError;
+
 // Re-export crates that are mandatory for the proc_macro to succeed
 pub use aws_config;
 pub use env_logger;
@@ -126,7 +137,7 @@ pub enum AppsyncAuthStrategy {
 }
 
 /// Identity information for Cognito User Pools authenticated requests.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppsyncIdentityCognito {
     /// Unique identifier of the authenticated user/client
@@ -156,7 +167,7 @@ pub enum CognitoIdentityAuthType {
 }
 
 /// Cognito Identity Pool information for federated IAM authentication
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct CognitoFederatedIdentity {
     /// Unique identifier assigned to the authenticated/unauthenticated identity
     /// within the Cognito Identity Pool
@@ -180,7 +191,7 @@ pub struct CognitoFederatedIdentity {
 ///
 /// Contains AWS IAM-specific authentication details, including optional Cognito
 /// identity pool information when using federated identities.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppsyncIdentityIam {
     /// AWS account ID of the caller
@@ -197,7 +208,7 @@ pub struct AppsyncIdentityIam {
 }
 
 /// Identity information for OIDC-authenticated requests.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct AppsyncIdentityOidc {
     /// The claims
     pub claims: AppsyncIdentityOidcClaims,
@@ -208,7 +219,7 @@ pub struct AppsyncIdentityOidc {
 }
 
 /// Claims information for OIDC-authenticated requests.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct AppsyncIdentityOidcClaims {
     /// The issuer of the token
     pub iss: String,
@@ -226,7 +237,7 @@ pub struct AppsyncIdentityOidcClaims {
 }
 
 /// Identity information for Lambda-authorized requests.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct AppsyncIdentityLambda {
     /// Custom resolver context returned by the Lambda authorizer
     #[serde(rename = "resolverContext")]
@@ -263,7 +274,7 @@ pub struct AppsyncIdentityLambda {
 /// custom authorization logic.
 ///
 /// More information can be found in the [AWS documentation](https://docs.aws.amazon.com/appsync/latest/devguide/security-authz.html).
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
 pub enum AppsyncIdentity {
     /// Amazon Cognito User Pools authentication
@@ -277,6 +288,111 @@ pub enum AppsyncIdentity {
     /// API Key authentication (represents null identity in JSON)
     ApiKey,
 }
+impl AppsyncIdentity {
+    /// Returns the AppSync authorization mode used to authenticate the request
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::{AppsyncAuthMode, AppsyncIdentity};
+    /// assert_eq!(AppsyncIdentity::ApiKey.auth_mode(), AppsyncAuthMode::ApiKey);
+    /// ```
+    pub fn auth_mode(&self) -> AppsyncAuthMode {
+        match self {
+            AppsyncIdentity::Cognito(_) => AppsyncAuthMode::CognitoUserPools,
+            AppsyncIdentity::Iam(_) => AppsyncAuthMode::Iam,
+            AppsyncIdentity::Oidc(_) => AppsyncAuthMode::Oidc,
+            AppsyncIdentity::Lambda(_) => AppsyncAuthMode::Lambda,
+            AppsyncIdentity::ApiKey => AppsyncAuthMode::ApiKey,
+        }
+    }
+
+    /// Checks this identity against the authorization directives of an operation
+    ///
+    /// The request is allowed when `allowed_auth_modes` is empty (the operation has no
+    /// authorization directive, so the default authorization mode of the API applies) or
+    /// contains the [auth_mode](AppsyncIdentity::auth_mode) of this identity. For Cognito User
+    /// Pools identities, the user must also belong to one of the `required_cognito_groups`
+    /// when it is not empty.
+    ///
+    /// These are the values returned by the `allowed_auth_modes()` and `required_cognito_groups()`
+    /// methods of the `Operation` enum generated by [appsync_lambda_main].
+    ///
+    /// # Errors
+    /// Returns an `Unauthorized` [AppsyncError] when the identity does not match.
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::{AppsyncAuthMode, AppsyncIdentity};
+    /// let identity = AppsyncIdentity::ApiKey;
+    /// assert!(identity.check_auth(&[], &[]).is_ok());
+    /// assert!(identity.check_auth(&[AppsyncAuthMode::ApiKey], &[]).is_ok());
+    /// assert!(identity.check_auth(&[AppsyncAuthMode::Iam], &[]).is_err());
+    /// ```
+    pub fn check_auth(
+        &self,
+        allowed_auth_modes: &[AppsyncAuthMode],
+        required_cognito_groups: &[&str],
+    ) -> Result<(), AppsyncError> {
+        if allowed_auth_modes.is_empty() {
+            return Ok(());
+        }
+        let auth_mode = self.auth_mode();
+        if !allowed_auth_modes.contains(&auth_mode) {
+            return Err(AppsyncError::new(
+                "Unauthorized",
+                format!("Not authorized to access this operation with {auth_mode} authorization"),
+            ));
+        }
+        if let AppsyncIdentity::Cognito(AppsyncIdentityCognito { groups, .. }) = self {
+            if !required_cognito_groups.is_empty()
+                && !groups.iter().flatten().any(|group| {
+                    required_cognito_groups
+                        .iter()
+                        .any(|required| required == group)
+                })
+            {
+                return Err(AppsyncError::new(
+                    "Unauthorized",
+                    format!(
+                        "Not authorized to access this operation, one of the Cognito groups {} is required",
+                        required_cognito_groups.join(", ")
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Authorization modes of an AppSync API.
+///
+/// Each variant corresponds to one of the AppSync authorization directives of a GraphQL schema,
+/// used to restrict the authorization modes allowed for a type or a field. The `Operation` enum
+/// generated by [appsync_lambda_main] exposes them through its `allowed_auth_modes()` method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AppsyncAuthMode {
+    /// API Key authorization (`@aws_api_key`)
+    ApiKey,
+    /// AWS IAM authorization (`@aws_iam`)
+    Iam,
+    /// OpenID Connect authorization (`@aws_oidc`)
+    Oidc,
+    /// Amazon Cognito User Pools authorization (`@aws_cognito_user_pools` or `@aws_auth`)
+    CognitoUserPools,
+    /// Lambda authorizer authorization (`@aws_lambda`)
+    Lambda,
+}
+impl core::fmt::Display for AppsyncAuthMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            AppsyncAuthMode::ApiKey => "API_KEY",
+            AppsyncAuthMode::Iam => "AWS_IAM",
+            AppsyncAuthMode::Oidc => "OPENID_CONNECT",
+            AppsyncAuthMode::CognitoUserPools => "AMAZON_COGNITO_USER_POOLS",
+            AppsyncAuthMode::Lambda => "AWS_LAMBDA",
+        })
+    }
+}
 
 /// Metadata about an AppSync GraphQL operation execution.
 ///
@@ -299,6 +415,15 @@ pub struct AppsyncEventInfo<O> {
     /// Variables passed to the GraphQL operation
     pub variables: HashMap<String, Value>,
 }
+impl<O> AppsyncEventInfo<O> {
+    /// Returns the fields selected by the request, parsed from
+    /// [selection_set_list](AppsyncEventInfo::selection_set_list)
+    ///
+    /// The selection set is empty if AppSync did not send the list.
+    pub fn selection_set(&self) -> SelectionSet {
+        self.selection_set_list.iter().flatten().collect()
+    }
+}
 
 /// Represents a complete AWS AppSync event sent to a Lambda resolver.
 ///
@@ -306,16 +431,18 @@ pub struct AppsyncEventInfo<O> {
 /// authentication details, operation info, and arguments. The generics `O`
 /// must be the Operation enum generated by the [appsync_lambda_main] macro.
 ///
-/// # Limitations
-/// - Omits the `stash` field used for pipeline resolvers
-/// - Omits the `prev` field as it's not relevant for direct Lambda resolvers
+/// When the Lambda function runs as a function of a pipeline resolver, the `stash` and `prev`
+/// fields hold the stash of the resolver and the result of the previous function, which
+/// [stash](AppsyncEvent::stash) and [prev_result](AppsyncEvent::prev_result) deserialize into
+/// user types.
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 pub struct AppsyncEvent<O> {
     /// Authentication context
     pub identity: AppsyncIdentity,
-    /// Raw request context from AppSync
-    pub request: Value,
+    /// Request context, with the HTTP headers of the request
+    #[serde(default)]
+    pub request: AppsyncRequest,
     /// Parent field's resolved value in nested resolvers
     pub source: Value,
     /// Metadata about the GraphQL operation
@@ -323,27 +450,103 @@ pub struct AppsyncEvent<O> {
     /// Arguments passed to the GraphQL field
     #[serde(rename = "arguments")]
     pub args: Value,
-    // Should never be usefull in a Direct Lambda Invocation context
-    // pub stash: Value,
-    // pub prev: Value,
+    /// Stash of the pipeline resolver, `null` for a unit resolver
+    #[serde(default)]
+    pub stash: Value,
+    /// Result of the previous function of the pipeline resolver, if any
+    #[serde(default)]
+    pub prev: Option<AppsyncPrev>,
+}
+impl<O> AppsyncEvent<O> {
+    /// Deserializes the stash of the pipeline resolver
+    ///
+    /// # Errors
+    /// Returns an `InvalidStash` [AppsyncError] if the stash is not the expected format
+    ///
+    /// # Examples
+    /// ```
+    /// # use lambda_appsync::AppsyncEvent;
+    /// # use serde::Deserialize;
+    /// # use serde_json::json;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #[derive(Deserialize)]
+    /// struct Stash {
+    ///     tenant: String,
+    /// }
+    ///
+    /// let event: AppsyncEvent<serde_json::Value> = serde_json::from_value(json!({
+    ///     "identity": null,
+    ///     "request": null,
+    ///     "source": null,
+    ///     "arguments": {},
+    ///     "info": {"parentTypeName": "Query", "fieldName": "cart", "variables": {}},
+    ///     "stash": {"tenant": "acme"},
+    ///     "prev": {"result": 42}
+    /// }))?;
+    /// let stash: Stash = event.stash()?;
+    /// assert_eq!(stash.tenant, "acme");
+    /// assert_eq!(event.prev_result::<u32>()?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stash<T: DeserializeOwned>(&self) -> Result<T, AppsyncError> {
+        T::deserialize(&self.stash).map_err(|e| {
+            AppsyncError::new(
+                "InvalidStash",
+                format!("Stash is not the expected format ({e})"),
+            )
+        })
+    }
+    /// Deserializes the result of the previous function of the pipeline resolver, which is
+    /// `null` when there is none
+    ///
+    /// # Errors
+    /// Returns an `InvalidPrevResult` [AppsyncError] if the result is not the expected format
+    pub fn prev_result<T: DeserializeOwned>(&self) -> Result<T, AppsyncError> {
+        let prev_result = self
+            .prev
+            .as_ref()
+            .map(|prev| &prev.result)
+            .unwrap_or(&Value::Null);
+        T::deserialize(prev_result).map_err(|e| {
+            AppsyncError::new(
+                "InvalidPrevResult",
+                format!("Previous result is not the expected format ({e})"),
+            )
+        })
+    }
+}
+
+/// The `prev` field of an [AppsyncEvent], set when the Lambda function runs after another
+/// function of a pipeline resolver
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppsyncPrev {
+    /// Result of the previous function
+    #[serde(default)]
+    pub result: Value,
 }
 
 /// Response structure returned to AWS AppSync from a Lambda resolver.
 ///
-/// Can contain either successful data or error information, but not both.
-/// Should be constructed using From implementations for either [Value] (success)
-/// or [AppsyncError] (failure).
+/// Can contain successful data, error information, or both for partial results.
+/// Should be constructed using From implementations for either [Value] (success),
+/// [AppsyncError] (failure) or [PartialData] (partial result).
 ///
 /// # Examples
 /// ```
 /// # use serde_json::json;
-/// # use lambda_appsync::{AppsyncError, AppsyncResponse};
+/// # use lambda_appsync::{AppsyncError, AppsyncResponse, PartialData};
 /// // Success response
 /// let response: AppsyncResponse = json!({ "id": 123 }).into();
 ///
 /// // Error response
 /// let error = AppsyncError::new("NotFound", "Resource not found");
 /// let response: AppsyncResponse = error.into();
+///
+/// // Partial response
+/// let partial = PartialData::new(json!([{ "id": 123 }]))
+///     .with_error(AppsyncError::new("PartialFailure", "Some items were not found"));
+/// let response: AppsyncResponse = partial.into();
 /// ```
 #[derive(Debug, Serialize)]
 pub struct AppsyncResponse {
@@ -366,6 +569,33 @@ impl AppsyncResponse {
     pub fn unauthorized() -> Self {
         AppsyncError::new("Unauthorized", "This operation cannot be authorized").into()
     }
+
+    /// Returns the data of the response
+    pub fn data(&self) -> Option<&Value> {
+        self.data.as_ref()
+    }
+    /// Returns a mutable reference to the data of the response, e.g. to mask some fields
+    pub fn data_mut(&mut self) -> Option<&mut Value> {
+        self.data.as_mut()
+    }
+    /// Returns the error of the response
+    pub fn error(&self) -> Option<&AppsyncError> {
+        self.error.as_ref()
+    }
+    /// Returns a mutable reference to the error of the response, e.g. to add error info
+    pub fn error_mut(&mut self) -> Option<&mut AppsyncError> {
+        self.error.as_mut()
+    }
+
+    /// Hides the details of the error of the response, see [AppsyncError::sanitize]
+    ///
+    /// The data of the response is kept.
+    pub fn sanitize_error(self, allowed_types: &[&str]) -> Self {
+        Self {
+            data: self.data,
+            error: self.error.map(|error| error.sanitize(allowed_types)),
+        }
+    }
 }
 
 impl From<Value> for AppsyncResponse {
@@ -384,6 +614,115 @@ impl From<AppsyncError> for AppsyncResponse {
         }
     }
 }
+impl From<PartialData<Value>> for AppsyncResponse {
+    fn from(value: PartialData<Value>) -> Self {
+        Self {
+            data: Some(value.data),
+            error: value.error,
+        }
+    }
+}
+
+/// Data returned by an operation together with an optional error.
+///
+/// AppSync Direct Lambda resolvers can return data and an error at the same time: the GraphQL
+/// response then contains both the data of the field and an entry in `errors`. Operation
+/// handlers return it through a [PartialResult], e.g. when only some items of a batch could be
+/// processed:
+///
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "../schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, AppsyncError, PartialData, PartialResult};
+/// use crate::Player;
+///
+/// # async fn dynamodb_get_players() -> Result<(Vec<Player>, Vec<String>), AppsyncError> {
+/// #    todo!()
+/// # }
+/// #[appsync_operation(query(players))]
+/// async fn get_players() -> PartialResult<Vec<Player>> {
+///     let (players, unreadable_ids) = dynamodb_get_players().await?;
+///     let mut data = PartialData::new(players);
+///     if !unreadable_ids.is_empty() {
+///         data = data.with_error(
+///             AppsyncError::new("PartialFailure", "Some players could not be read")
+///                 .with_error_info(serde_json::json!({ "ids": unreadable_ids })),
+///         );
+///     }
+///     Ok(data)
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct PartialData<T> {
+    /// The data of the field
+    pub data: T,
+    /// The error returned along with the data, if any
+    pub error: Option<AppsyncError>,
+}
+impl<T> PartialData<T> {
+    /// Creates partial data without error
+    pub fn new(data: T) -> Self {
+        Self { data, error: None }
+    }
+    /// Adds an error to return along with the data, combined with the previous one if any
+    pub fn with_error(mut self, error: AppsyncError) -> Self {
+        self.error = Some(match self.error {
+            Some(previous) => previous | error,
+            None => error,
+        });
+        self
+    }
+}
+impl<T> From<T> for PartialData<T> {
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+/// Result of an operation returning data together with an optional error, see [PartialData]
+pub type PartialResult<T> = Result<PartialData<T>, AppsyncError>;
+
+/// Converts the result of an operation handler to JSON partial data
+///
+/// Used by the code generated by [appsync_lambda_main], so that operation handlers can return
+/// either their data or a [PartialData].
+#[doc(hidden)]
+pub trait IntoPartialData {
+    /// Serializes the data, keeping the error if any
+    fn into_partial_data(self) -> PartialData<Value>;
+}
+impl<T: Serialize> IntoPartialData for T {
+    fn into_partial_data(self) -> PartialData<Value> {
+        PartialData::new(res_to_json(self))
+    }
+}
+impl<T: Serialize> IntoPartialData for PartialData<T> {
+    fn into_partial_data(self) -> PartialData<Value> {
+        PartialData {
+            data: res_to_json(self.data),
+            error: self.error,
+        }
+    }
+}
+
+/// Implemented by the return types of the handlers of the operations returning `T`
+///
+/// Used by the code generated by [appsync_lambda_main] to check the return type of the operation
+/// handlers, which return either their data or a [PartialData].
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid return type for an operation returning `{T}`",
+    label = "invalid operation handler",
+    note = "operation handlers return `Result<{T}, E>` or `Result<PartialData<{T}>, E>`, with an error `E` convertible into an `AppsyncError`"
+)]
+pub trait OperationResult<T> {}
+impl<T, E: Into<AppsyncError>> OperationResult<T> for Result<T, E> {}
+impl<T, E: Into<AppsyncError>> OperationResult<T> for Result<PartialData<T>, E> {}
 
 /// Error type for AWS AppSync operations
 ///
@@ -425,6 +764,9 @@ pub struct AppsyncError {
     pub error_type: String,
     /// A detailed message describing the specific error condition
     pub error_message: String,
+    /// Machine-readable details about the error, e.g. error codes or invalid fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_info: Option<Value>,
 }
 impl AppsyncError {
     /// Creates a new AppSync error with the specified error type and message
@@ -442,6 +784,98 @@ impl AppsyncError {
         AppsyncError {
             error_type: error_type.into(),
             error_message: error_message.into(),
+            error_info: None,
+        }
+    }
+    /// Sets the error info, returned to the client in the `errorInfo` of the GraphQL error
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AppsyncError;
+    /// # use serde_json::json;
+    /// let error = AppsyncError::new("ValidationError", "The input is invalid")
+    ///     .with_error_info(json!({ "code": "E_INVALID_INPUT" }));
+    /// assert_eq!(error.error_info, Some(json!({ "code": "E_INVALID_INPUT" })));
+    /// ```
+    pub fn with_error_info(mut self, error_info: impl Into<Value>) -> Self {
+        self.error_info = Some(error_info.into());
+        self
+    }
+    /// Sets the `key` entry of the error info, which becomes an object if it was not one
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AppsyncError;
+    /// # use serde_json::json;
+    /// let error = AppsyncError::new("ValidationError", "The input is invalid")
+    ///     .with_error_info_entry("code", "E_INVALID_INPUT")
+    ///     .with_error_info_entry("fields", json!(["email"]));
+    /// assert_eq!(
+    ///     error.error_info,
+    ///     Some(json!({ "code": "E_INVALID_INPUT", "fields": ["email"] }))
+    /// );
+    /// ```
+    pub fn with_error_info_entry(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<Value>,
+    ) -> Self {
+        let error_info = self
+            .error_info
+            .get_or_insert_with(|| Value::Object(Default::default()));
+        if !error_info.is_object() {
+            *error_info = Value::Object(Default::default());
+        }
+        if let Value::Object(entries) = error_info {
+            entries.insert(key.into(), value.into());
+        }
+        self
+    }
+}
+impl AppsyncError {
+    /// Hides the details of this error from the clients, unless its type is in `allowed_types`
+    ///
+    /// The full error is logged with a new correlation ID and replaced by a generic
+    /// `InternalError` whose `error_info` only holds that ID, so that the log entry of an error
+    /// reported by a client can be found. A combined error is kept only if all its types are
+    /// allowed.
+    ///
+    /// This is what the `error_policy = sanitize` option of
+    /// [appsync_lambda_main](crate::appsync_lambda_main) applies to the operation errors.
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::AppsyncError;
+    /// let error = AppsyncError::new("ResourceNotFoundException", "Requested resource not found: Table: players")
+    ///     .sanitize(&["NotFound"]);
+    /// assert_eq!(error.error_type, "InternalError");
+    /// assert!(error.error_info.unwrap()["correlationId"].is_string());
+    ///
+    /// let error = AppsyncError::new("NotFound", "No player with this ID").sanitize(&["NotFound"]);
+    /// assert_eq!(error.error_type, "NotFound");
+    /// ```
+    pub fn sanitize(self, allowed_types: &[&str]) -> Self {
+        if self
+            .error_type
+            .split('|')
+            .all(|error_type| allowed_types.contains(&error_type))
+        {
+            return self;
+        }
+        let correlation_id = ID::new().to_string();
+        log::error!("correlation_id={correlation_id} error={self:?}");
+        AppsyncError::new(
+            "InternalError",
+            format!("An internal error occurred (correlation ID: {correlation_id})"),
+        )
+        .with_error_info_entry("correlationId", correlation_id)
+    }
+    /// Creates an AppSync error from a domain error, see [AppsyncErrorKind]
+    pub fn from_error_kind<E: AppsyncErrorKind + ?Sized>(error: &E) -> Self {
+        AppsyncError {
+            error_type: error.error_type().to_owned(),
+            error_message: error.error_message(),
+            error_info: error.error_info(),
         }
     }
 }
@@ -451,16 +885,88 @@ impl<T: ProvideErrorMetadata> From<T> for AppsyncError {
         AppsyncError {
             error_type: meta.code().unwrap_or("Unknown").to_owned(),
             error_message: meta.message().unwrap_or_default().to_owned(),
+            error_info: None,
         }
     }
 }
 
+/// A domain error that can be returned to AppSync as an [AppsyncError].
+///
+/// Operation handlers can return `Result<T, E>` for any error type `E` implementing
+/// `Into<AppsyncError>`. Implementing this trait describes how `E` is reported to the clients,
+/// and the [AppsyncError](macro@AppsyncError) derive macro implements both this trait and the
+/// conversion.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::{AppsyncError, AppsyncErrorKind};
+/// # use serde_json::{json, Value};
+/// enum CartError {
+///     Empty,
+///     UnknownItem(String),
+/// }
+/// impl AppsyncErrorKind for CartError {
+///     fn error_type(&self) -> &str {
+///         match self {
+///             CartError::Empty => "EmptyCart",
+///             CartError::UnknownItem(_) => "NotFound",
+///         }
+///     }
+///     fn error_message(&self) -> String {
+///         match self {
+///             CartError::Empty => "The cart is empty".to_owned(),
+///             CartError::UnknownItem(id) => format!("No item `{id}`"),
+///         }
+///     }
+///     fn error_info(&self) -> Option<Value> {
+///         match self {
+///             CartError::Empty => None,
+///             CartError::UnknownItem(id) => Some(json!({ "itemId": id })),
+///         }
+///     }
+/// }
+/// impl From<CartError> for AppsyncError {
+///     fn from(error: CartError) -> Self {
+///         AppsyncError::from_error_kind(&error)
+///     }
+/// }
+///
+/// let error = AppsyncError::from(CartError::UnknownItem("42".to_owned()));
+/// assert_eq!(error.error_type, "NotFound");
+/// assert_eq!(error.error_message, "No item `42`");
+/// assert_eq!(error.error_info, Some(json!({ "itemId": "42" })));
+/// ```
+pub trait AppsyncErrorKind {
+    /// Returns the type/category of the error, the `errorType` of the GraphQL error
+    fn error_type(&self) -> &str;
+    /// Returns the message describing the error, the `errorMessage` of the GraphQL error
+    fn error_message(&self) -> String;
+    /// Returns the machine-readable details of the error, the `errorInfo` of the GraphQL error
+    fn error_info(&self) -> Option<Value> {
+        None
+    }
+}
+impl AppsyncErrorKind for AppsyncError {
+    fn error_type(&self) -> &str {
+        &self.error_type
+    }
+    fn error_message(&self) -> String {
+        self.error_message.clone()
+    }
+    fn error_info(&self) -> Option<Value> {
+        self.error_info.clone()
+    }
+}
+
+/// Only the `error_info` of the first combined error that has one is kept.
 impl BitOr for AppsyncError {
     type Output = AppsyncError;
     fn bitor(self, rhs: Self) -> Self::Output {
         AppsyncError {
             error_type: format!("{}|{}", self.error_type, rhs.error_type),
             error_message: format!("{}\n{}", self.error_message, rhs.error_message),
+            // Only one error info can be returned, the first one is kept
+            error_info: self.error_info.or(rhs.error_info),
         }
     }
 }
@@ -520,6 +1026,169 @@ pub fn arg_from_json<T: DeserializeOwned>(
     })
 }
 
+/// Extracts and deserializes a named argument from a JSON Value into the specified type,
+/// falling back to its default value when the argument is omitted
+///
+/// This is used for the arguments with a default value in the GraphQL schema. Like in GraphQL,
+/// only an omitted argument gets the default value, an explicit `null` is deserialized as is.
+///
+/// # Arguments
+/// * `args` - Mutable reference to a JSON Value containing arguments
+/// * `arg_name` - Name of the argument to extract
+/// * `default_value` - JSON Value used when the argument is omitted
+///
+/// # Returns
+/// * `Ok(T)` - Successfully deserialized value of type T
+/// * `Err(AppsyncError)` - Error if the argument or its default value has an invalid format
+///
+/// # Examples
+/// ```
+/// # use serde_json::json;
+/// # use lambda_appsync::arg_from_json_or;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut args = json!({
+///     "limit": 5,
+///     "offset": null
+/// });
+///
+/// let limit: i32 = arg_from_json_or(&mut args, "limit", json!(20))?;
+/// assert_eq!(limit, 5);
+///
+/// // Only missing arguments get the default value, null arguments are kept
+/// let offset: Option<i32> = arg_from_json_or(&mut args, "offset", json!(0))?;
+/// assert_eq!(offset, None);
+/// let order: String = arg_from_json_or(&mut args, "order", json!("ASC"))?;
+/// assert_eq!(order, "ASC");
+/// # Ok(())
+/// # }
+/// ```
+pub fn arg_from_json_or<T: DeserializeOwned>(
+    args: &mut serde_json::Value,
+    arg_name: &'static str,
+    default_value: serde_json::Value,
+) -> Result<T, AppsyncError> {
+    let value = args
+        .get_mut(arg_name)
+        .map(serde_json::Value::take)
+        .unwrap_or(default_value);
+    serde_json::from_value(value).map_err(|e| {
+        AppsyncError::new(
+            "InvalidArgs",
+            format!("Argument \"{arg_name}\" is not the expected format ({e})"),
+        )
+    })
+}
+
+// Deserializes an input field with a default value, for the generated input types: serde
+// default functions cannot fail, and the default value may not match a `type_override`
+#[doc(hidden)]
+pub fn input_field_from_json_or<T: DeserializeOwned>(
+    value: Maybe<serde_json::Value>,
+    field_name: &'static str,
+    default_value: serde_json::Value,
+) -> Result<T, String> {
+    let value = match value {
+        Maybe::Undefined => {
+            return serde_json::from_value(default_value).map_err(|e| {
+                format!("The default value of field \"{field_name}\" does not match its type ({e})")
+            })
+        }
+        Maybe::Null => serde_json::Value::Null,
+        Maybe::Value(value) => value,
+    };
+    serde_json::from_value(value)
+        .map_err(|e| format!("Field \"{field_name}\" is not the expected format ({e})"))
+}
+
+/// Extracts and deserializes a named nullable argument from a JSON Value into a [Maybe],
+/// telling an omitted argument from an argument explicitly set to `null`
+///
+/// This is used for the nullable arguments with the `nullable_inputs_as_maybe` option.
+///
+/// # Arguments
+/// * `args` - Mutable reference to a JSON Value containing arguments
+/// * `arg_name` - Name of the argument to extract
+///
+/// # Returns
+/// * `Ok(Maybe<T>)` - [Maybe::Undefined] if the argument is omitted, [Maybe::Null] if it is
+///   `null` and [Maybe::Value] otherwise
+/// * `Err(AppsyncError)` - Error if the argument has an invalid format
+///
+/// # Examples
+/// ```
+/// # use serde_json::json;
+/// # use lambda_appsync::{arg_from_json_maybe, Maybe};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut args = json!({
+///     "name": "Ferris",
+///     "nickname": null
+/// });
+///
+/// let name: Maybe<String> = arg_from_json_maybe(&mut args, "name")?;
+/// assert_eq!(name, Maybe::Value("Ferris".to_owned()));
+/// let nickname: Maybe<String> = arg_from_json_maybe(&mut args, "nickname")?;
+/// assert_eq!(nickname, Maybe::Null);
+/// let team: Maybe<String> = arg_from_json_maybe(&mut args, "team")?;
+/// assert_eq!(team, Maybe::Undefined);
+/// # Ok(())
+/// # }
+/// ```
+pub fn arg_from_json_maybe<T: DeserializeOwned>(
+    args: &mut serde_json::Value,
+    arg_name: &'static str,
+) -> Result<Maybe<T>, AppsyncError> {
+    match args.get_mut(arg_name) {
+        None => Ok(Maybe::Undefined),
+        Some(value) => serde_json::from_value(value.take()).map_err(|e| {
+            AppsyncError::new(
+                "InvalidArgs",
+                format!("Argument \"{arg_name}\" is not the expected format ({e})"),
+            )
+        }),
+    }
+}
+
+/// Deserializes the parent value of a nested field resolver from the event `source`
+///
+/// # Arguments
+/// * `source` - Mutable reference to the JSON Value of the parent, it is taken
+///
+/// # Returns
+/// * `Ok(T)` - Successfully deserialized parent of type T
+/// * `Err(AppsyncError)` - Error if the source is missing or not the expected format
+///
+/// # Examples
+/// ```
+/// # use serde::Deserialize;
+/// # use serde_json::json;
+/// # use lambda_appsync::source_from_json;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// #[derive(Deserialize)]
+/// struct Player {
+///     id: String,
+/// }
+///
+/// let mut source = json!({ "id": "123" });
+/// let player: Player = source_from_json(&mut source)?;
+/// assert_eq!(player.id, "123");
+///
+/// // Error case: no source
+/// let result: Result<Player, _> = source_from_json(&mut json!(null));
+/// assert!(result.is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn source_from_json<T: DeserializeOwned>(
+    source: &mut serde_json::Value,
+) -> Result<T, AppsyncError> {
+    serde_json::from_value(source.take()).map_err(|e| {
+        AppsyncError::new(
+            "InvalidSource",
+            format!("Source is not the expected format ({e})"),
+        )
+    })
+}
+
 /// Serializes a value into a JSON Value for AppSync responses
 ///
 /// # Arguments
@@ -681,35 +1350,45 @@ mod tests {
     #[test]
     fn test_appsync_identity_oidc() {
         let json = json!({
-            "iss": "https://auth.example.com",
+            "claims": {
+                "iss": "https://auth.example.com",
+                "sub": "user123",
+                "aud": "client123",
+                "exp": 1714521210,
+                "iat": 1714517610,
+                "name": "John Doe",
+                "email": "john@example.com",
+                "roles": ["admin"],
+                "org_id": "org123",
+                "custom_claim": "value"
+            },
             "sub": "user123",
-            "aud": "client123",
-            "exp": 1714521210,
-            "iat": 1714517610,
-            "name": "John Doe",
-            "email": "john@example.com",
-            "roles": ["admin"],
-            "org_id": "org123",
-            "custom_claim": "value"
+            "issuer": "https://auth.example.com"
         });
 
         if let AppsyncIdentity::Oidc(oidc) = serde_json::from_value(json).unwrap() {
-            assert_eq!(oidc.iss, "https://auth.example.com");
             assert_eq!(oidc.sub, "user123");
-            assert_eq!(oidc.aud, "client123");
-            assert_eq!(oidc.exp, 1714521210);
-            assert_eq!(oidc.iat, 1714517610);
-            assert_eq!(oidc.additional_claims.get("name").unwrap(), "John Doe");
+            assert_eq!(oidc.issuer, "https://auth.example.com");
+            let claims = oidc.claims;
+            assert_eq!(claims.iss, "https://auth.example.com");
+            assert_eq!(claims.sub, "user123");
+            assert_eq!(claims.aud, "client123");
+            assert_eq!(claims.exp, 1714521210);
+            assert_eq!(claims.iat, 1714517610);
+            assert_eq!(claims.additional_claims.get("name").unwrap(), "John Doe");
             assert_eq!(
-                oidc.additional_claims.get("email").unwrap(),
+                claims.additional_claims.get("email").unwrap(),
                 "john@example.com"
             );
             assert_eq!(
-                oidc.additional_claims.get("roles").unwrap(),
+                claims.additional_claims.get("roles").unwrap(),
                 &json!(["admin"])
             );
-            assert_eq!(oidc.additional_claims.get("org_id").unwrap(), "org123");
-            assert_eq!(oidc.additional_claims.get("custom_claim").unwrap(), "value");
+            assert_eq!(claims.additional_claims.get("org_id").unwrap(), "org123");
+            assert_eq!(
+                claims.additional_claims.get("custom_claim").unwrap(),
+                "value"
+            );
         } else {
             panic!("Expected OIDC variant");
         }
@@ -756,6 +1435,96 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_appsync_identity_check_auth() {
+        let cognito: AppsyncIdentity = serde_json::from_value(json!({
+            "sub": "user123",
+            "username": "testuser",
+            "issuer": "https://cognito-idp.region.amazonaws.com/pool_id",
+            "defaultAuthStrategy": "ALLOW",
+            "sourceIp": ["1.2.3.4"],
+            "groups": ["users"],
+            "claims": {}
+        }))
+        .unwrap();
+        assert_eq!(cognito.auth_mode(), AppsyncAuthMode::CognitoUserPools);
+
+        // No directive
+        assert!(cognito.check_auth(&[], &["admin"]).is_ok());
+        // Allowed mode, with or without groups
+        assert!(cognito
+            .check_auth(&[AppsyncAuthMode::CognitoUserPools], &[])
+            .is_ok());
+        assert!(cognito
+            .check_auth(&[AppsyncAuthMode::CognitoUserPools], &["admin", "users"])
+            .is_ok());
+        // Missing group
+        let err = cognito
+            .check_auth(&[AppsyncAuthMode::CognitoUserPools], &["admin"])
+            .unwrap_err();
+        assert_eq!(err.error_type, "Unauthorized");
+        // Mode not allowed
+        let err = cognito
+            .check_auth(&[AppsyncAuthMode::ApiKey, AppsyncAuthMode::Iam], &[])
+            .unwrap_err();
+        assert_eq!(err.error_type, "Unauthorized");
+
+        // Groups only concern Cognito User Pools identities
+        let api_key = AppsyncIdentity::ApiKey;
+        assert!(api_key
+            .check_auth(
+                &[AppsyncAuthMode::ApiKey, AppsyncAuthMode::CognitoUserPools],
+                &["admin"]
+            )
+            .is_ok());
+        assert!(api_key
+            .check_auth(&[AppsyncAuthMode::CognitoUserPools], &[])
+            .is_err());
+    }
+
+    #[test]
+    fn test_appsync_event_stash_and_prev() {
+        let event = |extra: Value| {
+            let mut event = json!({
+                "identity": null,
+                "request": null,
+                "source": null,
+                "arguments": {},
+                "info": {"parentTypeName": "Query", "fieldName": "field", "variables": {}}
+            });
+            event
+                .as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value::<AppsyncEvent<Value>>(event).unwrap()
+        };
+
+        // Unit resolver
+        let unit = event(json!({}));
+        assert_eq!(unit.stash, Value::Null);
+        assert!(unit.prev.is_none());
+        assert_eq!(unit.stash::<Option<String>>().unwrap(), None);
+        assert_eq!(unit.prev_result::<Option<String>>().unwrap(), None);
+
+        // Pipeline function
+        let pipeline = event(json!({
+            "stash": {"count": 2},
+            "prev": {"result": ["a", "b"]}
+        }));
+        assert_eq!(
+            pipeline.stash::<HashMap<String, u32>>().unwrap()["count"],
+            2
+        );
+        assert_eq!(
+            pipeline.prev_result::<Vec<String>>().unwrap(),
+            vec!["a".to_owned(), "b".to_owned()]
+        );
+        let error = pipeline.stash::<Vec<String>>().unwrap_err();
+        assert_eq!(error.error_type, "InvalidStash");
+        let error = pipeline.prev_result::<u32>().unwrap_err();
+        assert_eq!(error.error_type, "InvalidPrevResult");
+    }
+
     #[test]
     fn test_appsync_response() {
         let success = AppsyncResponse::from(json!({"field": "value"}));
@@ -781,6 +1550,85 @@ mod tests {
         assert_eq!(combined.error_message, "msg1\nmsg2");
     }
 
+    #[test]
+    fn test_appsync_error_info() {
+        let error = AppsyncError::new("ValidationError", "message")
+            .with_error_info("not an object")
+            .with_error_info_entry("code", 42);
+        assert_eq!(error.error_info, Some(json!({"code": 42})));
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            json!({"errorType": "ValidationError", "errorMessage": "message", "errorInfo": {"code": 42}})
+        );
+        // No `errorInfo` without error info
+        assert_eq!(
+            serde_json::to_value(AppsyncError::new("Error", "message")).unwrap(),
+            json!({"errorType": "Error", "errorMessage": "message"})
+        );
+
+        let combined = AppsyncError::new("Error1", "msg1")
+            | AppsyncError::new("Error2", "msg2").with_error_info(json!(2))
+            | AppsyncError::new("Error3", "msg3").with_error_info(json!(3));
+        assert_eq!(combined.error_info, Some(json!(2)));
+    }
+
+    #[test]
+    fn test_partial_data_response() {
+        let partial = PartialData::new(vec![1, 2])
+            .with_error(AppsyncError::new("Error1", "msg1"))
+            .with_error(AppsyncError::new("Error2", "msg2").with_error_info(json!({"ids": [3]})));
+        let response = AppsyncResponse::from(partial.into_partial_data());
+        assert_eq!(
+            serde_json::to_value(response).unwrap(),
+            json!({
+                "data": [1, 2],
+                "errorType": "Error1|Error2",
+                "errorMessage": "msg1\nmsg2",
+                "errorInfo": {"ids": [3]}
+            })
+        );
+        let response = AppsyncResponse::from(42.into_partial_data());
+        assert_eq!(serde_json::to_value(response).unwrap(), json!({"data": 42}));
+    }
+
+    #[test]
+    fn test_sanitize_error() {
+        let allowed = ["NotFound", "ValidationError"];
+        let error = AppsyncError::new("NotFound", "msg").with_error_info(json!({"id": 1}));
+        let error = error.sanitize(&allowed);
+        assert_eq!(error.error_type, "NotFound");
+        assert_eq!(error.error_info, Some(json!({"id": 1})));
+
+        let error =
+            AppsyncError::new("NotFound", "msg1") | AppsyncError::new("ValidationError", "msg2");
+        assert_eq!(
+            error.sanitize(&allowed).error_type,
+            "NotFound|ValidationError"
+        );
+
+        let error = AppsyncError::new("NotFound", "msg1")
+            | AppsyncError::new("ThrottlingException", "arn:aws:dynamodb:table/players");
+        let error = error.sanitize(&allowed);
+        assert_eq!(error.error_type, "InternalError");
+        let correlation_id = error.error_info.as_ref().unwrap()["correlationId"]
+            .as_str()
+            .unwrap();
+        assert!(ID::try_from(correlation_id.to_owned()).is_ok());
+        assert!(error.error_message.contains(correlation_id));
+        assert!(!error.error_message.contains("arn"));
+
+        let response = AppsyncResponse::from(
+            PartialData::new(json!([1])).with_error(AppsyncError::new("Unknown", "msg")),
+        )
+        .sanitize_error(&[]);
+        let response = serde_json::to_value(response).unwrap();
+        assert_eq!(response["data"], json!([1]));
+        assert_eq!(response["errorType"], "InternalError");
+
+        let response = AppsyncResponse::from(json!(42)).sanitize_error(&[]);
+        assert_eq!(serde_json::to_value(response).unwrap(), json!({"data": 42}));
+    }
+
     #[test]
     fn test_arg_from_json() {
         let mut args = json!({
@@ -802,6 +1650,47 @@ mod tests {
         assert!(err.is_err());
     }
 
+    #[test]
+    fn test_arg_from_json_or() {
+        let mut args = json!({
+            "number": 42,
+            "null": null
+        });
+
+        let n: i32 = arg_from_json_or(&mut args, "number", json!(20)).unwrap();
+        assert_eq!(n, 42);
+
+        let n: Option<i32> = arg_from_json_or(&mut args, "null", json!(20)).unwrap();
+        assert_eq!(n, None);
+
+        let s: String = arg_from_json_or(&mut args, "missing", json!("default")).unwrap();
+        assert_eq!(s, "default");
+
+        let err: Result<String, _> = arg_from_json_or(&mut args, "missing", json!(20));
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_arg_from_json_maybe() {
+        let mut args = json!({
+            "value": 42,
+            "null": null
+        });
+
+        let n: Maybe<i32> = arg_from_json_maybe(&mut args, "value").unwrap();
+        assert_eq!(n, Maybe::Value(42));
+
+        let n: Maybe<i32> = arg_from_json_maybe(&mut args, "null").unwrap();
+        assert_eq!(n, Maybe::Null);
+
+        let n: Maybe<i32> = arg_from_json_maybe(&mut args, "missing").unwrap();
+        assert_eq!(n, Maybe::Undefined);
+
+        args["value"] = json!("not a number");
+        let err: Result<Maybe<i32>, _> = arg_from_json_maybe(&mut args, "value");
+        assert!(err.is_err());
+    }
+
     #[test]
     fn test_res_to_json() {
         #[derive(Serialize)]
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A nullable GraphQL input value that tells an omitted value from an explicit `null`.
+///
+/// `Option<T>` cannot make the difference between a field that is absent from the input and a
+/// field explicitly set to `null`, which partial update mutations need: the first leaves the
+/// stored value untouched while the second clears it. The [appsync_lambda_main](crate::appsync_lambda_main)
+/// option `nullable_inputs_as_maybe = true` uses this type for the nullable input fields and
+/// operation arguments.
+///
+/// It deserializes from `null` as [Null](Maybe::Null) and from any other value as
+/// [Value](Maybe::Value), while [Undefined](Maybe::Undefined) is its [Default] value, used by
+/// serde when the field is absent. Serializing a [Maybe] gives `null` unless it is a
+/// [Value](Maybe::Value), the generated structs skip their [Undefined](Maybe::Undefined) fields.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::Maybe;
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct PlayerUpdate {
+///     #[serde(default)]
+///     nickname: Maybe<String>,
+/// }
+///
+/// let update: PlayerUpdate = serde_json::from_str("{}").unwrap();
+/// assert_eq!(update.nickname, Maybe::Undefined);
+///
+/// let update: PlayerUpdate = serde_json::from_str(r#"{"nickname": null}"#).unwrap();
+/// assert_eq!(update.nickname, Maybe::Null);
+///
+/// let update: PlayerUpdate = serde_json::from_str(r#"{"nickname": "Ferris"}"#).unwrap();
+/// assert_eq!(update.nickname, Maybe::Value("Ferris".to_owned()));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Maybe<T> {
+    /// The value was omitted
+    #[default]
+    Undefined,
+    /// The value was explicitly set to `null`
+    Null,
+    /// The value was set
+    Value(T),
+}
+impl<T> Maybe<T> {
+    /// Returns `true` if the value was omitted
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, Maybe::Undefined)
+    }
+    /// Returns `true` if the value was explicitly set to `null`
+    pub fn is_null(&self) -> bool {
+        matches!(self, Maybe::Null)
+    }
+    /// Returns `true` if the value was set
+    pub fn is_value(&self) -> bool {
+        matches!(self, Maybe::Value(_))
+    }
+    /// Converts from `&Maybe<T>` to `Maybe<&T>`
+    pub fn as_ref(&self) -> Maybe<&T> {
+        match self {
+            Maybe::Undefined => Maybe::Undefined,
+            Maybe::Null => Maybe::Null,
+            Maybe::Value(value) => Maybe::Value(value),
+        }
+    }
+    /// Converts from `&mut Maybe<T>` to `Maybe<&mut T>`
+    pub fn as_mut(&mut self) -> Maybe<&mut T> {
+        match self {
+            Maybe::Undefined => Maybe::Undefined,
+            Maybe::Null => Maybe::Null,
+            Maybe::Value(value) => Maybe::Value(value),
+        }
+    }
+    /// Converts from `&Maybe<T>` to `Maybe<&T::Target>`
+    pub fn as_deref(&self) -> Maybe<&T::Target>
+    where
+        T: core::ops::Deref,
+    {
+        self.as_ref().map(|value| value.deref())
+    }
+    /// Maps a `Maybe<T>` to a `Maybe<U>` by applying a function to the contained value
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Maybe<U> {
+        match self {
+            Maybe::Undefined => Maybe::Undefined,
+            Maybe::Null => Maybe::Null,
+            Maybe::Value(value) => Maybe::Value(f(value)),
+        }
+    }
+    /// Returns the contained value, if any, losing the difference between omitted and `null`
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Maybe::Undefined | Maybe::Null => None,
+            Maybe::Value(value) => Some(value),
+        }
+    }
+    /// Applies this value as a partial update of `target`: an omitted value leaves it
+    /// untouched, `null` clears it and a set value replaces it
+    ///
+    /// # Example
+    /// ```
+    /// # use lambda_appsync::Maybe;
+    /// let mut nickname = Some("Ferris".to_owned());
+    /// Maybe::Undefined.update(&mut nickname);
+    /// assert_eq!(nickname.as_deref(), Some("Ferris"));
+    /// Maybe::Value("Crab".to_owned()).update(&mut nickname);
+    /// assert_eq!(nickname.as_deref(), Some("Crab"));
+    /// Maybe::Null.update(&mut nickname);
+    /// assert_eq!(nickname, None);
+    /// ```
+    pub fn update(self, target: &mut Option<T>) {
+        match self {
+            Maybe::Undefined => (),
+            Maybe::Null => *target = None,
+            Maybe::Value(value) => *target = Some(value),
+        }
+    }
+}
+impl<T> From<Option<T>> for Maybe<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            None => Maybe::Null,
+            Some(value) => Maybe::Value(value),
+        }
+    }
+}
+impl<T: Serialize> Serialize for Maybe<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Maybe::Undefined | Maybe::Null => serializer.serialize_none(),
+            Maybe::Value(value) => serializer.serialize_some(value),
+        }
+    }
+}
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Maybe<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<T>::deserialize(deserializer).map(Maybe::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Update {
+        #[serde(default, skip_serializing_if = "Maybe::is_undefined")]
+        name: Maybe<String>,
+    }
+
+    #[test]
+    fn test_maybe_deserialize() {
+        let update: Update = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(update.name, Maybe::Undefined);
+
+        let update: Update = serde_json::from_value(json!({"name": null})).unwrap();
+        assert_eq!(update.name, Maybe::Null);
+
+        let update: Update = serde_json::from_value(json!({"name": "test"})).unwrap();
+        assert_eq!(update.name, Maybe::Value("test".to_owned()));
+
+        assert!(serde_json::from_value::<Update>(json!({"name": 42})).is_err());
+    }
+
+    #[test]
+    fn test_maybe_serialize() {
+        let update = Update {
+            name: Maybe::Undefined,
+        };
+        assert_eq!(serde_json::to_value(update).unwrap(), json!({}));
+
+        let update = Update { name: Maybe::Null };
+        assert_eq!(serde_json::to_value(update).unwrap(), json!({"name": null}));
+
+        let update = Update {
+            name: Maybe::Value("test".to_owned()),
+        };
+        assert_eq!(
+            serde_json::to_value(update).unwrap(),
+            json!({"name": "test"})
+        );
+    }
+
+    #[test]
+    fn test_maybe_conversions() {
+        assert_eq!(Maybe::from(Some(1)), Maybe::Value(1));
+        assert_eq!(Maybe::<i32>::from(None), Maybe::Null);
+        assert_eq!(Maybe::Value(1).map(|v| v + 1).into_option(), Some(2));
+        assert_eq!(Maybe::<i32>::Null.into_option(), None);
+        assert_eq!(Maybe::<i32>::Undefined.into_option(), None);
+        assert_eq!(Maybe::Value(Box::new(1)).as_deref(), Maybe::Value(&1));
+    }
+}
@@ -0,0 +1,287 @@
+use serde_json::Value;
+
+use crate::AppsyncError;
+
+/// The GraphQL type of an operation result or of an object field, as declared in the schema.
+///
+/// The [appsync_lambda_main](crate::appsync_lambda_main) option `check_responses = true`
+/// generates these types for the schema and checks the serialized result of every query,
+/// mutation and field resolver against them before responding to AppSync. A `null` value where
+/// the schema declares a non-null type, or a value that is not a list where it declares a list,
+/// is turned into an `InvalidResponse` [AppsyncError] naming the faulty path, instead of the
+/// opaque error AppSync would report.
+///
+/// Fields missing from a serialized object are not checked, as they may be resolved by another
+/// resolver.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::{ObjectType, OutputType};
+/// # use serde_json::json;
+/// static PLAYER: ObjectType = ObjectType {
+///     name: "Player",
+///     fields: &[("name", OutputType::NonNull(&OutputType::Leaf("String")))],
+/// };
+/// // [Player!]!
+/// static PLAYERS: OutputType =
+///     OutputType::NonNull(&OutputType::List(&OutputType::NonNull(&OutputType::Object(&PLAYER))));
+///
+/// assert!(PLAYERS.check(&json!([{"name": "Ferris"}]), "players").is_ok());
+///
+/// let error = PLAYERS.check(&json!([{"name": null}]), "players").unwrap_err();
+/// assert_eq!(error.error_type, "InvalidResponse");
+/// assert_eq!(
+///     error.error_message,
+///     "`players[0].name` is null but its type `String!` is non-null"
+/// );
+/// ```
+pub enum OutputType {
+    /// A non-null type
+    NonNull(&'static OutputType),
+    /// A list type
+    List(&'static OutputType),
+    /// A scalar or enum type, with its name
+    Leaf(&'static str),
+    /// An object type
+    Object(&'static ObjectType),
+    /// A union or interface type, resolved with the `__typename` of the value
+    Abstract {
+        /// Name of the union or interface
+        name: &'static str,
+        /// Object types of the union members or of the interface implementors
+        types: &'static [&'static ObjectType],
+    },
+}
+
+/// A GraphQL object type, see [OutputType]
+pub struct ObjectType {
+    /// Name of the type
+    pub name: &'static str,
+    /// Names and types of the fields of the type
+    pub fields: &'static [(&'static str, OutputType)],
+}
+
+// Object types are usually recursive, so only their names are displayed
+impl core::fmt::Debug for OutputType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "OutputType({self})")
+    }
+}
+impl core::fmt::Debug for ObjectType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ObjectType({})", self.name)
+    }
+}
+
+impl core::fmt::Display for OutputType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            OutputType::NonNull(inner) => write!(f, "{inner}!"),
+            OutputType::List(inner) => write!(f, "[{inner}]"),
+            OutputType::Leaf(name) | OutputType::Abstract { name, .. } => f.write_str(name),
+            OutputType::Object(object_type) => f.write_str(object_type.name),
+        }
+    }
+}
+
+// Location of a value in the result, only formatted when reporting an error
+enum PathSegment<'a> {
+    Field(&'a str),
+    Index(usize),
+}
+fn format_path(path: &[PathSegment<'_>]) -> String {
+    let mut formatted = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Field(name) => {
+                if !formatted.is_empty() {
+                    formatted.push('.');
+                }
+                formatted.push_str(name);
+            }
+            PathSegment::Index(index) => formatted.push_str(&format!("[{index}]")),
+        }
+    }
+    formatted
+}
+
+impl OutputType {
+    /// Checks the serialized result `value` of the field `field_name` against this type
+    ///
+    /// # Errors
+    /// Returns an `InvalidResponse` [AppsyncError] for the first `null` value of a non-null type
+    /// or the first value that does not have the shape of its type
+    pub fn check(&self, value: &Value, field_name: &str) -> Result<(), AppsyncError> {
+        let mut path = vec![PathSegment::Field(field_name)];
+        self.check_at(value, &mut path)
+            .map_err(|message| AppsyncError::new("InvalidResponse", message))
+    }
+    fn check_at<'a>(
+        &self,
+        value: &'a Value,
+        path: &mut Vec<PathSegment<'a>>,
+    ) -> Result<(), String> {
+        match (self, value) {
+            (OutputType::NonNull(_), Value::Null) => Err(format!(
+                "`{}` is null but its type `{self}` is non-null",
+                format_path(path)
+            )),
+            (OutputType::NonNull(inner), value) => inner.check_at(value, path),
+            (_, Value::Null) => Ok(()),
+            (OutputType::List(inner), Value::Array(items)) => {
+                for (index, item) in items.iter().enumerate() {
+                    path.push(PathSegment::Index(index));
+                    inner.check_at(item, path)?;
+                    path.pop();
+                }
+                Ok(())
+            }
+            (OutputType::List(_), _) => Err(format!(
+                "`{}` is not a list but its type is `{self}`",
+                format_path(path)
+            )),
+            // `AWSJSON` values can be lists
+            (OutputType::Leaf(name), Value::Array(_)) if *name != "AWSJSON" => Err(format!(
+                "`{}` is a list but its type `{self}` is not",
+                format_path(path)
+            )),
+            (OutputType::Leaf(_), _) => Ok(()),
+            (OutputType::Object(object_type), Value::Object(fields)) => {
+                object_type.check_at(fields, path)
+            }
+            (OutputType::Abstract { types, .. }, Value::Object(fields)) => {
+                let typename = fields.get("__typename").and_then(Value::as_str);
+                match types.iter().find(|t| Some(t.name) == typename) {
+                    Some(object_type) => object_type.check_at(fields, path),
+                    None => Err(format!(
+                        "`{}` has no `__typename` of a type of `{self}`",
+                        format_path(path)
+                    )),
+                }
+            }
+            (OutputType::Object(_) | OutputType::Abstract { .. }, _) => Err(format!(
+                "`{}` is not an object but its type is `{self}`",
+                format_path(path)
+            )),
+        }
+    }
+}
+impl ObjectType {
+    fn check_at<'a>(
+        &self,
+        values: &'a serde_json::Map<String, Value>,
+        path: &mut Vec<PathSegment<'a>>,
+    ) -> Result<(), String> {
+        for (name, output_type) in self.fields {
+            // Missing fields may be resolved by another resolver
+            if let Some((name, value)) = values.get_key_value(*name) {
+                path.push(PathSegment::Field(name));
+                output_type.check_at(value, path)?;
+                path.pop();
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    static TEAM: ObjectType = ObjectType {
+        name: "Team",
+        fields: &[
+            ("name", OutputType::NonNull(&OutputType::Leaf("String"))),
+            (
+                "players",
+                OutputType::List(&OutputType::NonNull(&OutputType::Object(&PLAYER))),
+            ),
+        ],
+    };
+    static PLAYER: ObjectType = ObjectType {
+        name: "Player",
+        fields: &[
+            ("name", OutputType::NonNull(&OutputType::Leaf("String"))),
+            ("nickname", OutputType::Leaf("String")),
+            ("team", OutputType::Object(&TEAM)),
+        ],
+    };
+    static COACH: ObjectType = ObjectType {
+        name: "Coach",
+        fields: &[("name", OutputType::NonNull(&OutputType::Leaf("String")))],
+    };
+    static PERSON: OutputType = OutputType::NonNull(&OutputType::Abstract {
+        name: "Person",
+        types: &[&PLAYER, &COACH],
+    });
+    static PLAYERS: OutputType = OutputType::NonNull(&OutputType::List(&OutputType::NonNull(
+        &OutputType::Object(&PLAYER),
+    )));
+
+    fn error_message(output_type: &OutputType, value: Value) -> String {
+        output_type
+            .check(&value, "field")
+            .unwrap_err()
+            .error_message
+    }
+
+    #[test]
+    fn test_output_type_display() {
+        assert_eq!(PLAYERS.to_string(), "[Player!]!");
+        assert_eq!(PERSON.to_string(), "Person!");
+    }
+
+    #[test]
+    fn test_output_type_check_valid() {
+        let players = json!([
+            {"name": "Ferris", "nickname": null, "team": {"name": "Rust", "players": []}},
+            // Missing fields are not checked
+            {"name": "Crab"}
+        ]);
+        assert!(PLAYERS.check(&players, "players").is_ok());
+        assert!(PERSON
+            .check(&json!({"__typename": "Coach", "name": "Coach"}), "person")
+            .is_ok());
+        assert!(OutputType::Leaf("AWSJSON")
+            .check(&json!([1, 2]), "json")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_output_type_check_invalid() {
+        let error = PLAYERS.check(&Value::Null, "players").unwrap_err();
+        assert_eq!(error.error_type, "InvalidResponse");
+        assert_eq!(
+            error.error_message,
+            "`players` is null but its type `[Player!]!` is non-null"
+        );
+        assert_eq!(
+            error_message(&PLAYERS, json!([{"name": "Ferris"}, null])),
+            "`field[1]` is null but its type `Player!` is non-null"
+        );
+        assert_eq!(
+            error_message(
+                &PLAYERS,
+                json!([{"name": "Ferris", "team": {"name": "Rust", "players": [{"name": null}]}}])
+            ),
+            "`field[0].team.players[0].name` is null but its type `String!` is non-null"
+        );
+        assert_eq!(
+            error_message(&PLAYERS, json!({"name": "Ferris"})),
+            "`field` is not a list but its type is `[Player!]`"
+        );
+        assert_eq!(
+            error_message(&PLAYERS, json!(["Ferris"])),
+            "`field[0]` is not an object but its type is `Player`"
+        );
+        assert_eq!(
+            error_message(&PLAYERS, json!([{"name": ["Ferris"]}])),
+            "`field[0].name` is a list but its type `String` is not"
+        );
+        assert_eq!(
+            error_message(&PERSON, json!({"__typename": "Referee", "name": "Referee"})),
+            "`field` has no `__typename` of a type of `Person`"
+        );
+    }
+}
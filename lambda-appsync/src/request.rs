@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+/// HTTP headers of an AppSync request, with case-insensitive names.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::AppsyncHeaders;
+/// let headers: AppsyncHeaders = [("X-Tenant-Id", "acme")].into_iter().collect();
+/// assert_eq!(headers.get("x-tenant-id"), Some("acme"));
+/// assert_eq!(headers.get("X-TENANT-ID"), Some("acme"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AppsyncHeaders(HashMap<String, String>);
+impl AppsyncHeaders {
+    /// Returns the value of the header `name`, regardless of its case
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+    /// Returns `true` if the header `name` is present, regardless of its case
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(&name.to_ascii_lowercase())
+    }
+    /// Iterates over the headers, with lowercase names
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+    /// Returns the number of headers
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Returns `true` if there is no header
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for AppsyncHeaders {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(name, value)| (name.into().to_ascii_lowercase(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Request context of an [AppsyncEvent](crate::AppsyncEvent).
+///
+/// Holds the HTTP headers of the GraphQL request and the custom domain name it was sent to, if
+/// any. The raw request context received from AppSync is kept in [raw](AppsyncRequest::raw) for
+/// anything else. A `null` request context, e.g. for an invocation that did not come from an
+/// HTTP request, gives no header and no domain name.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::AppsyncRequest;
+/// # use serde_json::json;
+/// let request: AppsyncRequest = serde_json::from_value(json!({
+///     "headers": {
+///         "User-Agent": "Mozilla/5.0",
+///         "x-forwarded-for": "203.0.113.7, 10.0.0.1",
+///         "x-tenant-id": "acme"
+///     },
+///     "domainName": "api.example.com"
+/// }))
+/// .unwrap();
+/// assert_eq!(request.user_agent(), Some("Mozilla/5.0"));
+/// assert_eq!(request.client_ip(), Some("203.0.113.7"));
+/// assert_eq!(request.header("X-Tenant-Id"), Some("acme"));
+/// assert_eq!(request.domain_name.as_deref(), Some("api.example.com"));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct AppsyncRequest {
+    /// HTTP headers of the request
+    pub headers: AppsyncHeaders,
+    /// Custom domain name the request was sent to, `None` for the default AppSync endpoint
+    pub domain_name: Option<String>,
+    /// Raw request context from AppSync
+    pub raw: Value,
+}
+impl AppsyncRequest {
+    /// Returns the value of the header `name`, regardless of its case
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)
+    }
+    /// Returns the `user-agent` header
+    pub fn user_agent(&self) -> Option<&str> {
+        self.header("user-agent")
+    }
+    /// Returns the `host` header
+    pub fn host(&self) -> Option<&str> {
+        self.header("host")
+    }
+    /// Returns the `origin` header
+    pub fn origin(&self) -> Option<&str> {
+        self.header("origin")
+    }
+    /// Iterates over the addresses of the `x-forwarded-for` header, starting with the client
+    pub fn forwarded_for(&self) -> impl Iterator<Item = &str> {
+        self.header("x-forwarded-for")
+            .into_iter()
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+    }
+    /// Returns the client address, the first address of the `x-forwarded-for` header
+    pub fn client_ip(&self) -> Option<&str> {
+        self.forwarded_for().next()
+    }
+}
+impl From<Value> for AppsyncRequest {
+    fn from(raw: Value) -> Self {
+        // Headers with a non-string value are not HTTP headers, they stay reachable in `raw`
+        let headers = raw
+            .get("headers")
+            .and_then(Value::as_object)
+            .map(|headers| {
+                headers
+                    .iter()
+                    .filter_map(|(name, value)| Some((name.as_str(), value.as_str()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let domain_name = raw
+            .get("domainName")
+            .and_then(Value::as_str)
+            .map(str::to_owned);
+        Self {
+            headers,
+            domain_name,
+            raw,
+        }
+    }
+}
+impl<'de> Deserialize<'de> for AppsyncRequest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_appsync_request_headers() {
+        let request: AppsyncRequest = serde_json::from_value(json!({
+            "headers": {
+                "Host": "example.appsync-api.eu-west-1.amazonaws.com",
+                "X-Forwarded-For": " 203.0.113.7 ,10.0.0.1",
+                "x-count": 3
+            },
+            "domainName": null,
+            "other": "value"
+        }))
+        .unwrap();
+        assert_eq!(request.headers.len(), 2);
+        assert!(request.headers.contains("HOST"));
+        assert_eq!(
+            request.host(),
+            Some("example.appsync-api.eu-west-1.amazonaws.com")
+        );
+        assert_eq!(
+            request.forwarded_for().collect::<Vec<_>>(),
+            ["203.0.113.7", "10.0.0.1"]
+        );
+        assert_eq!(request.client_ip(), Some("203.0.113.7"));
+        assert_eq!(request.user_agent(), None);
+        assert_eq!(request.domain_name, None);
+        assert_eq!(request.raw["headers"]["x-count"], 3);
+        assert_eq!(request.raw["other"], "value");
+    }
+
+    #[test]
+    fn test_appsync_request_null() {
+        let request: AppsyncRequest = serde_json::from_value(Value::Null).unwrap();
+        assert!(request.headers.is_empty());
+        assert_eq!(request.domain_name, None);
+        assert_eq!(request.client_ip(), None);
+        assert_eq!(request.raw, Value::Null);
+    }
+}
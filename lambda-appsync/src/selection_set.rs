@@ -0,0 +1,219 @@
+use std::{collections::BTreeMap, marker::PhantomData};
+
+/// The fields selected by a GraphQL request, as a tree.
+///
+/// It is built from the `selectionSetList` of the [AppsyncEventInfo](crate::AppsyncEventInfo),
+/// whose entries are the `/`-separated paths of the selected fields, with
+/// [AppsyncEventInfo::selection_set](crate::AppsyncEventInfo::selection_set). Lookups also accept
+/// `/`-separated paths.
+///
+/// # Example
+/// ```
+/// # use lambda_appsync::SelectionSet;
+/// let selection_set: SelectionSet = ["id", "team", "stats", "stats/goals"].into_iter().collect();
+/// assert!(selection_set.is_selected("team"));
+/// assert!(selection_set.is_selected("stats/goals"));
+/// assert!(!selection_set.is_selected("nickname"));
+///
+/// let stats = selection_set.sub("stats").unwrap();
+/// assert!(stats.is_selected("goals"));
+/// assert!(!stats.is_selected("assists"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SelectionSet(BTreeMap<String, SelectionSet>);
+impl SelectionSet {
+    /// Returns `true` if the field at `path` is selected
+    pub fn is_selected(&self, path: &str) -> bool {
+        self.sub(path).is_some()
+    }
+    /// Returns the selection set of the field at `path`, if it is selected
+    ///
+    /// The selection set of a selected leaf field is empty.
+    pub fn sub(&self, path: &str) -> Option<&SelectionSet> {
+        path.split('/')
+            .try_fold(self, |selection_set, name| selection_set.0.get(name))
+    }
+    /// Iterates over the names of the selected fields, in alphabetical order
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+    /// Returns `true` if no field is selected
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    fn insert(&mut self, path: &str) {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .fold(self, |selection_set, name| {
+                selection_set.0.entry(name.to_owned()).or_default()
+            });
+    }
+}
+impl<S: AsRef<str>> FromIterator<S> for SelectionSet {
+    fn from_iter<I: IntoIterator<Item = S>>(paths: I) -> Self {
+        let mut selection_set = Self::default();
+        for path in paths {
+            selection_set.insert(path.as_ref());
+        }
+        selection_set
+    }
+}
+
+/// The fields of a GraphQL type, as a Rust enum.
+///
+/// The [appsync_lambda_main](crate::appsync_lambda_main) macro implements it on a
+/// `<Type>FieldName` enum generated for each GraphQL `type`, used by [SelectedFields].
+pub trait SelectableField: Copy + 'static {
+    /// All the fields of the type
+    const ALL: &'static [Self];
+    /// Returns the GraphQL name of the field
+    fn name(self) -> &'static str;
+}
+
+/// A [SelectionSet] checked with the fields of a GraphQL type, see [SelectableField].
+///
+/// The generated types returned by an operation have a `selected_fields` function returning the
+/// fields selected by the request of an [AppsyncEvent](crate::AppsyncEvent), so that a resolver can
+/// skip loading the fields the client did not ask for. The fields of the nested types are given by
+/// the accessors of the `<Type>SelectedFields` trait generated for the types with fields of another
+/// type, e.g. `fields.stats()` returning the `SelectedFields<PlayerStatsFieldName>` of `stats`.
+///
+/// # Example
+/// ```no_run
+/// # lambda_appsync::appsync_lambda_main!(
+/// #    "../schema.graphql",
+/// #     exclude_lambda_handler = true,
+/// # );
+/// # mod sub {
+/// use lambda_appsync::{appsync_operation, AppsyncError, AppsyncEvent, ID};
+/// use crate::{Operation, Player, PlayerFieldName};
+///
+/// # async fn get_player_with_team(id: ID) -> Result<Option<Player>, AppsyncError> { todo!() }
+/// # async fn get_player_without_team(id: ID) -> Result<Option<Player>, AppsyncError> { todo!() }
+/// #[appsync_operation(query(player), with_appsync_event)]
+/// async fn get_player(id: ID, event: &AppsyncEvent<Operation>) -> Result<Option<Player>, AppsyncError> {
+///     if Player::selected_fields(event).is_selected(PlayerFieldName::Team) {
+///         get_player_with_team(id).await
+///     } else {
+///         get_player_without_team(id).await
+///     }
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectedFields<F> {
+    selection_set: SelectionSet,
+    fields: PhantomData<F>,
+}
+impl<F: SelectableField> SelectedFields<F> {
+    /// Wraps the selection set of a value of the type of `F`
+    pub fn new(selection_set: SelectionSet) -> Self {
+        Self {
+            selection_set,
+            fields: PhantomData,
+        }
+    }
+    /// Returns `true` if `field` is selected
+    pub fn is_selected(&self, field: F) -> bool {
+        self.selection_set.is_selected(field.name())
+    }
+    /// Returns the selection set of `field`, if it is selected
+    pub fn sub(&self, field: F) -> Option<&SelectionSet> {
+        self.selection_set.0.get(field.name())
+    }
+    /// Iterates over the selected fields, in declaration order
+    pub fn iter(&self) -> impl Iterator<Item = F> + '_ {
+        F::ALL
+            .iter()
+            .copied()
+            .filter(|field| self.is_selected(*field))
+    }
+    /// Returns the underlying selection set
+    pub fn selection_set(&self) -> &SelectionSet {
+        &self.selection_set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum PlayerFieldName {
+        Id,
+        Team,
+        Stats,
+    }
+    impl SelectableField for PlayerFieldName {
+        const ALL: &'static [Self] = &[Self::Id, Self::Team, Self::Stats];
+        fn name(self) -> &'static str {
+            match self {
+                Self::Id => "id",
+                Self::Team => "team",
+                Self::Stats => "stats",
+            }
+        }
+    }
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum TeamFieldName {
+        Name,
+        Players,
+    }
+    impl SelectableField for TeamFieldName {
+        const ALL: &'static [Self] = &[Self::Name, Self::Players];
+        fn name(self) -> &'static str {
+            match self {
+                Self::Name => "name",
+                Self::Players => "players",
+            }
+        }
+    }
+
+    #[test]
+    fn test_selection_set() {
+        // Parents of nested fields are always listed by AppSync, but this is not required
+        let selection_set: SelectionSet =
+            ["stats", "id", "team/players/name"].into_iter().collect();
+        assert_eq!(
+            selection_set.fields().collect::<Vec<_>>(),
+            ["id", "stats", "team"]
+        );
+        assert!(selection_set.is_selected("team/players"));
+        assert!(!selection_set.is_selected("team/name"));
+        assert!(!selection_set.is_selected("id/name"));
+        assert!(selection_set.sub("stats").unwrap().is_empty());
+        assert_eq!(
+            selection_set
+                .sub("team")
+                .and_then(|team| team.sub("players"))
+                .map(|players| players.fields().collect::<Vec<_>>()),
+            Some(vec!["name"])
+        );
+        assert!(SelectionSet::from_iter([""; 0]).is_empty());
+    }
+
+    #[test]
+    fn test_selected_fields() {
+        let selection_set: SelectionSet = ["team", "team/name", "id"].into_iter().collect();
+        let fields = SelectedFields::<PlayerFieldName>::new(selection_set);
+        assert!(fields.is_selected(PlayerFieldName::Id));
+        assert!(!fields.is_selected(PlayerFieldName::Stats));
+        assert_eq!(
+            fields.iter().collect::<Vec<_>>(),
+            [PlayerFieldName::Id, PlayerFieldName::Team]
+        );
+
+        let team_fields = SelectedFields::<TeamFieldName>::new(
+            fields
+                .sub(PlayerFieldName::Team)
+                .cloned()
+                .unwrap_or_default(),
+        );
+        assert_eq!(
+            team_fields.iter().collect::<Vec<_>>(),
+            [TeamFieldName::Name]
+        );
+        assert_eq!(fields.sub(PlayerFieldName::Stats), None);
+    }
+}
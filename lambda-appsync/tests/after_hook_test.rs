@@ -0,0 +1,124 @@
+use std::sync::Mutex;
+
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, AppsyncError, AppsyncIdentity, AppsyncResponse, ID,
+};
+use serde_json::json;
+
+mod common;
+use common::Event;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/partial_updates.graphql",
+    batch = false,
+    after_hook = audit,
+    error_policy = sanitize("NotFound"),
+);
+
+static AUDIT_LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn is_admin(identity: &AppsyncIdentity) -> bool {
+    match identity {
+        AppsyncIdentity::Cognito(cognito) => cognito
+            .groups
+            .as_ref()
+            .is_some_and(|groups| groups.iter().any(|group| group == "admin")),
+        _ => false,
+    }
+}
+
+async fn audit(
+    operation: &Operation,
+    identity: &AppsyncIdentity,
+    mut response: AppsyncResponse,
+) -> AppsyncResponse {
+    AUDIT_LOG.lock().unwrap().push(format!(
+        "{operation:?} {}",
+        response
+            .error()
+            .map(|error| error.error_type.as_str())
+            .unwrap_or("OK")
+    ));
+    // Only the admins can see the nicknames
+    if !is_admin(identity) {
+        if let Some(players) = response.data_mut().and_then(|data| data.as_array_mut()) {
+            for player in players {
+                player["nickname"] = serde_json::Value::Null;
+            }
+        }
+    }
+    if let Some(error) = response.error_mut() {
+        error.error_info = Some(json!({"operation": format!("{operation:?}")}));
+    }
+    response
+}
+
+#[appsync_operation(query(players))]
+async fn get_players(team: Option<Team>) -> Result<Vec<Player>, AppsyncError> {
+    match team {
+        Some(Team::Js) => Err(AppsyncError::new("NotFound", "No such team")),
+        Some(Team::Python) => Err(AppsyncError::new("ThrottlingException", "Rate exceeded")),
+        _ => Ok(vec![Player {
+            id: ID::new(),
+            name: "Ferris".to_owned(),
+            nickname: Some("Crab".to_owned()),
+            team: Some(Team::Rust),
+        }]),
+    }
+}
+
+fn cognito_identity(groups: &[&str]) -> serde_json::Value {
+    json!({
+        "sub": "user123",
+        "username": "testuser",
+        "issuer": "https://cognito-idp.region.amazonaws.com/pool_id",
+        "defaultAuthStrategy": "ALLOW",
+        "sourceIp": ["1.2.3.4"],
+        "groups": groups,
+        "claims": {}
+    })
+}
+
+#[tokio::test]
+async fn test_after_hook() {
+    let response = Event::query("players")
+        .identity(cognito_identity(&["admin"]))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"][0]["nickname"], "Crab");
+    let response = Event::query("players")
+        .identity(cognito_identity(&["users"]))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"][0]["name"], "Ferris");
+    assert_eq!(response["data"][0]["nickname"], json!(null));
+
+    let response = Event::query("players")
+        .arguments(json!({"team": "JS"}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "NotFound");
+    assert_eq!(
+        response["errorInfo"],
+        json!({"operation": "Query(Players)"})
+    );
+
+    // The hook sees the errors before they are sanitized
+    let response = Event::query("players")
+        .arguments(json!({"team": "PYTHON"}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "InternalError");
+    assert!(response["errorInfo"].get("operation").is_none());
+
+    let audit_log = AUDIT_LOG.lock().unwrap();
+    assert_eq!(
+        *audit_log,
+        [
+            "Query(Players) OK",
+            "Query(Players) OK",
+            "Query(Players) NotFound",
+            "Query(Players) ThrottlingException"
+        ]
+    );
+}
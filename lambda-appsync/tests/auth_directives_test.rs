@@ -0,0 +1,180 @@
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, AppsyncAuthMode, AppsyncError, AppsyncEvent, ID,
+};
+use serde_json::json;
+
+// Generate AppSync types and runtime, rejecting identities not matching the auth directives
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/auth_directives.graphql",
+    batch = false,
+    field_resolver = Player.score,
+    check_auth_directives = true,
+);
+
+#[appsync_operation(mutation(deletePlayer))]
+async fn delete_player(id: ID) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id,
+        name: "Test Player".to_owned(),
+        score: None,
+    })
+}
+
+#[appsync_operation(field(Player.score))]
+async fn player_score(player: Player) -> Result<i32, AppsyncError> {
+    Ok(player.name.len() as i32)
+}
+
+fn operation(parent_type_name: &str, field_name: &str) -> Operation {
+    serde_json::from_value::<AppsyncEvent<Operation>>(json!({
+        "info": {
+            "fieldName": field_name,
+            "parentTypeName": parent_type_name,
+            "variables": {}
+        },
+        "arguments": {},
+        "identity": null,
+        "request": null,
+        "source": null
+    }))
+    .unwrap()
+    .info
+    .operation
+}
+
+fn cognito_identity(groups: &[&str]) -> serde_json::Value {
+    json!({
+        "sub": "user123",
+        "issuer": "cognito",
+        "username": "testuser",
+        "claims": {},
+        "groups": groups,
+        "sourceIp": ["1.1.1.1"],
+        "defaultAuthStrategy": "ALLOW"
+    })
+}
+
+fn delete_player_event(identity: serde_json::Value) -> serde_json::Value {
+    json!({
+        "info": {
+            "fieldName": "deletePlayer",
+            "parentTypeName": "Mutation",
+            "variables": {}
+        },
+        "arguments": {
+            "id": ID::new().to_string()
+        },
+        "identity": identity,
+        "request": null,
+        "source": null
+    })
+}
+
+#[test]
+fn test_allowed_auth_modes() {
+    // Type directives
+    assert_eq!(
+        operation("Query", "players").allowed_auth_modes(),
+        &[AppsyncAuthMode::ApiKey, AppsyncAuthMode::CognitoUserPools]
+    );
+    // Field directives replace type directives
+    assert_eq!(
+        operation("Query", "player").allowed_auth_modes(),
+        &[AppsyncAuthMode::Iam]
+    );
+    // No directive
+    assert!(operation("Mutation", "createPlayer")
+        .allowed_auth_modes()
+        .is_empty());
+    assert_eq!(
+        operation("Mutation", "deletePlayer").allowed_auth_modes(),
+        &[AppsyncAuthMode::CognitoUserPools, AppsyncAuthMode::Iam]
+    );
+    assert_eq!(
+        operation("Mutation", "banPlayer").allowed_auth_modes(),
+        &[
+            AppsyncAuthMode::CognitoUserPools,
+            AppsyncAuthMode::Lambda,
+            AppsyncAuthMode::Oidc
+        ]
+    );
+    // Field resolvers use the directives of their parent type
+    assert_eq!(
+        operation("Player", "score").allowed_auth_modes(),
+        &[AppsyncAuthMode::CognitoUserPools]
+    );
+}
+
+#[test]
+fn test_required_cognito_groups() {
+    assert!(operation("Query", "players")
+        .required_cognito_groups()
+        .is_empty());
+    assert!(operation("Mutation", "createPlayer")
+        .required_cognito_groups()
+        .is_empty());
+    assert_eq!(
+        operation("Mutation", "deletePlayer").required_cognito_groups(),
+        &["admin", "moderator"]
+    );
+    assert_eq!(
+        operation("Mutation", "banPlayer").required_cognito_groups(),
+        &["admin"]
+    );
+    assert_eq!(
+        operation("Player", "score").required_cognito_groups(),
+        &["players"]
+    );
+}
+
+#[tokio::test]
+async fn test_check_auth_directives_allowed() {
+    let event = delete_player_event(cognito_identity(&["moderator"]));
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value["data"]["name"], "Test Player");
+}
+
+#[tokio::test]
+async fn test_check_auth_directives_missing_group() {
+    let event = delete_player_event(cognito_identity(&["players"]));
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert!(response_value["data"].is_null());
+    assert_eq!(response_value["errorType"], "Unauthorized");
+}
+
+#[tokio::test]
+async fn test_check_auth_directives_mode_not_allowed() {
+    // API key identity
+    let event = delete_player_event(serde_json::Value::Null);
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value["errorType"], "Unauthorized");
+}
+
+#[tokio::test]
+async fn test_check_auth_directives_field_resolver() {
+    let event = json!({
+        "info": {
+            "fieldName": "score",
+            "parentTypeName": "Player",
+            "variables": {}
+        },
+        "arguments": {},
+        "identity": cognito_identity(&["players"]),
+        "request": null,
+        "source": {"id": ID::new().to_string(), "name": "Test Player", "score": 0}
+    });
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value["data"], 11);
+}
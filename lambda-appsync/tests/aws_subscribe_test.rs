@@ -0,0 +1,47 @@
+use lambda_appsync::appsync_lambda_main;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/subscriptions.graphql",
+    batch = false,
+);
+
+fn subscription_names(mutation: MutationField) -> Vec<String> {
+    mutation
+        .subscriptions()
+        .iter()
+        .map(|subscription| format!("{subscription:?}"))
+        .collect()
+}
+
+#[test]
+fn test_mutation_subscriptions() {
+    assert_eq!(
+        subscription_names(MutationField::CreatePlayer),
+        ["OnCreatePlayer", "OnPlayerChange"]
+    );
+    assert_eq!(
+        subscription_names(MutationField::UpdatePlayer),
+        ["OnPlayerChange"]
+    );
+    assert_eq!(
+        subscription_names(MutationField::DeletePlayer),
+        ["OnPlayerChange"]
+    );
+    assert_eq!(
+        subscription_names(MutationField::ResetScores),
+        ["OnResetScores"]
+    );
+}
+
+#[test]
+fn test_mutation_subscriptions_match() {
+    // The lookup can be matched on to decide what a mutation must do for its subscribers
+    let invalidates_player_filters = |mutation: MutationField| {
+        mutation
+            .subscriptions()
+            .iter()
+            .any(|subscription| matches!(subscription, SubscriptionField::OnPlayerChange))
+    };
+    assert!(invalidates_player_filters(MutationField::DeletePlayer));
+    assert!(!invalidates_player_filters(MutationField::ResetScores));
+}
@@ -0,0 +1,79 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/builders.graphql",
+    batch = false,
+    builders = true,
+);
+
+#[appsync_operation(query(players))]
+async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+    Ok(vec![])
+}
+
+#[appsync_operation(mutation(createPlayer))]
+async fn create_player(input: PlayerInput) -> Result<Player, AppsyncError> {
+    Ok(Player::builder()
+        .id(ID::new())
+        .name(input.name)
+        .tags(vec![])
+        .nickname(input.nickname)
+        .team(input.team)
+        .build())
+}
+
+#[test]
+fn test_builder_required_and_optional_fields() {
+    let id = ID::new();
+    // Required fields can be set in any order, optional ones default to `None`
+    let player = Player::builder()
+        .tags(vec!["rust".to_owned()])
+        .name("Ferris")
+        .id(id)
+        .build();
+    assert_eq!(player.id, id);
+    assert_eq!(player.name, "Ferris");
+    assert_eq!(player.tags, vec!["rust".to_owned()]);
+    assert!(player.nickname.is_none());
+    assert!(player.team.is_none());
+    assert!(player.mentor.is_none());
+
+    let mentor = player.clone();
+    let player = Player::builder()
+        .id(ID::new())
+        .name("Crab".to_owned())
+        .tags(vec![])
+        .nickname("Crabby".to_owned())
+        .team(Some(Team::Rust))
+        .mentor(Box::new(mentor))
+        .build();
+    assert_eq!(player.nickname.as_deref(), Some("Crabby"));
+    assert_eq!(player.team, Some(Team::Rust));
+    assert_eq!(player.mentor().map(|mentor| mentor.id), Some(id));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_builder_deprecated_field() {
+    let player = Player::builder()
+        .id(ID::new())
+        .name("Ferris")
+        .tags(vec![])
+        .score(42)
+        .build();
+    assert_eq!(player.score, Some(42));
+}
+
+#[test]
+fn test_builder_default_values() {
+    let input = PlayerInput::builder().name("Ferris").build();
+    assert_eq!(input.team, Team::Rust);
+    assert!(input.nickname.is_none());
+
+    let input = PlayerInput::builder().team(Team::Js).name("Ferris").build();
+    assert_eq!(input.team, Team::Js);
+
+    // Without non-null fields, the builder can build right away
+    let filter = PlayerFilter::builder().build();
+    assert!(filter.name.is_none() && filter.team.is_none());
+}
@@ -0,0 +1,155 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError};
+use serde_json::{json, Value};
+
+mod common;
+use common::Event;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/check_responses.graphql",
+    batch = false,
+    check_responses = true,
+    field_resolver = Player.rank,
+    type_override = Query.player: Value,
+    type_override = Query.players: Value,
+    type_override = Query.search: Value,
+    type_override = Mutation.renamePlayer: Value,
+    type_override = Player.rank: Value,
+);
+
+#[appsync_operation(query(player))]
+async fn player(value: Value) -> Result<Value, AppsyncError> {
+    Ok(value)
+}
+
+#[appsync_operation(query(players))]
+async fn players(value: Value) -> Result<Vec<Value>, AppsyncError> {
+    Ok(serde_json::from_value(value).unwrap())
+}
+
+#[appsync_operation(query(search))]
+async fn search(value: Value) -> Result<Option<Vec<Value>>, AppsyncError> {
+    Ok(serde_json::from_value(value).unwrap())
+}
+
+#[appsync_operation(query(teams))]
+async fn teams() -> Result<Vec<Team>, AppsyncError> {
+    Ok(vec![Team::Rust, Team::Python])
+}
+
+#[appsync_operation(mutation(renamePlayer))]
+async fn rename_player(value: Value) -> Result<Option<Value>, AppsyncError> {
+    Ok(Some(value))
+}
+
+#[appsync_operation(field(Player.rank))]
+async fn player_rank(player: Player) -> Result<Value, AppsyncError> {
+    // Players without a team are not ranked
+    Ok(match player.team {
+        Some(_) => json!(1),
+        None => Value::Null,
+    })
+}
+
+async fn query(field_name: &str, value: Value) -> Value {
+    Event::query(field_name)
+        .arguments(json!({ "value": value }))
+        .call(function_handler)
+        .await
+}
+
+fn player_json(name: Value) -> Value {
+    json!({"id": "b2e5ce5c-2b5b-4cc6-bb7a-7a1ee1ddcc22", "name": name})
+}
+
+#[tokio::test]
+async fn test_valid_responses() {
+    let response = query("player", player_json(json!("Ferris"))).await;
+    assert_eq!(response["data"]["name"], "Ferris");
+
+    let mut friend = player_json(json!("Crab"));
+    friend["friends"] = Value::Null;
+    let mut player = player_json(json!("Ferris"));
+    player["friends"] = json!([friend]);
+    let response = query("players", json!([player])).await;
+    assert_eq!(response["data"][0]["friends"][0]["name"], "Crab");
+
+    let response = query("search", Value::Null).await;
+    assert_eq!(response["data"], Value::Null);
+
+    let response = Event::query("teams").call(function_handler).await;
+    assert_eq!(response["data"], json!(["RUST", "PYTHON"]));
+
+    let response = Event::mutation("renamePlayer")
+        .arguments(json!({ "value": null }))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_invalid_responses() {
+    let response = query("player", Value::Null).await;
+    assert_eq!(response["errorType"], "InvalidResponse");
+    assert_eq!(
+        response["errorMessage"],
+        "`player` is null but its type `Player!` is non-null"
+    );
+
+    let response = query("player", player_json(Value::Null)).await;
+    assert_eq!(
+        response["errorMessage"],
+        "`player.name` is null but its type `String!` is non-null"
+    );
+
+    let mut player = player_json(json!("Ferris"));
+    player["friends"] = json!([player_json(json!("Crab")), null]);
+    let response = query("players", json!([player])).await;
+    assert_eq!(
+        response["errorMessage"],
+        "`players[0].friends[1]` is null but its type `Player!` is non-null"
+    );
+
+    let mut player = player_json(json!("Ferris"));
+    player["friends"] = player_json(json!("Crab"));
+    let response = query("players", json!([player])).await;
+    assert_eq!(
+        response["errorMessage"],
+        "`players[0].friends` is not a list but its type is `[Player!]`"
+    );
+
+    let response = query("search", json!([{"__typename": "Game", "title": null}])).await;
+    assert_eq!(
+        response["errorMessage"],
+        "`search[0].title` is null but its type `String!` is non-null"
+    );
+
+    let response = Event::mutation("renamePlayer")
+        .arguments(json!({ "value": ["Ferris"] }))
+        .call(function_handler)
+        .await;
+    assert_eq!(
+        response["errorMessage"],
+        "`renamePlayer` is not an object but its type is `Player`"
+    );
+}
+
+#[tokio::test]
+async fn test_field_resolver_response() {
+    let mut player = player_json(json!("Ferris"));
+    player["team"] = json!("RUST");
+    let response = Event::new("Player", "rank")
+        .source(player.clone())
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"], 1);
+
+    player["team"] = Value::Null;
+    let response = Event::new("Player", "rank")
+        .source(player)
+        .call(function_handler)
+        .await;
+    assert_eq!(
+        response["errorMessage"],
+        "`rank` is null but its type `Int!` is non-null"
+    );
+}
@@ -0,0 +1,82 @@
+//! AppSync events of the integration tests, sent to the generated `function_handler`
+
+// Each test only uses some of the helpers
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+use std::future::Future;
+
+use lambda_runtime::LambdaEvent;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Event of an operation, without arguments, identity, request or source until they are set
+pub struct Event(Value);
+impl Event {
+    pub fn new(parent_type_name: &str, field_name: &str) -> Self {
+        Self(json!({
+            "info": {
+                "fieldName": field_name,
+                "parentTypeName": parent_type_name,
+                "variables": {},
+                "selectionSetList": [],
+                "selectionSetGraphQL": ""
+            },
+            "arguments": {},
+            "identity": null,
+            "request": null,
+            "source": null
+        }))
+    }
+    pub fn query(field_name: &str) -> Self {
+        Self::new("Query", field_name)
+    }
+    pub fn mutation(field_name: &str) -> Self {
+        Self::new("Mutation", field_name)
+    }
+    /// Paths of the selected fields, e.g. `["id", "stats", "stats/goals"]`
+    pub fn selection_set(mut self, selection_set_list: &[&str]) -> Self {
+        self.0["info"]["selectionSetList"] = json!(selection_set_list);
+        self
+    }
+    pub fn arguments(mut self, arguments: Value) -> Self {
+        self.0["arguments"] = arguments;
+        self
+    }
+    pub fn identity(mut self, identity: Value) -> Self {
+        self.0["identity"] = identity;
+        self
+    }
+    /// Headers of the request of the client
+    pub fn headers(mut self, headers: Value) -> Self {
+        self.0["request"] = json!({ "headers": headers });
+        self
+    }
+    pub fn source(mut self, source: Value) -> Self {
+        self.0["source"] = source;
+        self
+    }
+    /// Stash of the AppSync pipeline resolver
+    pub fn stash(mut self, stash: Value) -> Self {
+        self.0["stash"] = stash;
+        self
+    }
+    /// Result of the previous function of the AppSync pipeline resolver
+    pub fn prev(mut self, prev: Value) -> Self {
+        self.0["prev"] = prev;
+        self
+    }
+    /// Calls the `function_handler` generated by `appsync_lambda_main!` with the event and
+    /// returns its response as JSON
+    pub async fn call<F, Fut, R, E>(self, function_handler: F) -> Value
+    where
+        F: FnOnce(LambdaEvent<Value>) -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+        R: Serialize,
+        E: Debug,
+    {
+        let lambda_event = LambdaEvent::new(self.0, Default::default());
+        let response = function_handler(lambda_event).await.unwrap();
+        serde_json::to_value(response).unwrap()
+    }
+}
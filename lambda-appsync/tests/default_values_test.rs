@@ -0,0 +1,161 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::json;
+
+mod common;
+use common::Event;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/default_values.graphql",
+    batch = false,
+);
+
+// Nullable arguments with a default value stay nullable, an explicit null is not replaced
+#[appsync_operation(query(players))]
+async fn get_players(
+    limit: Option<i32>,
+    offset: i32,
+    team: Option<Team>,
+    name: Option<String>,
+) -> Result<Vec<Player>, AppsyncError> {
+    let limit = limit.unwrap_or(i32::MAX).min(3);
+    Ok((offset..offset + limit)
+        .map(|i| Player {
+            id: ID::new(),
+            name: format!("{}{i}", name.as_deref().unwrap_or("Player")),
+            team: team.unwrap_or(Team::Python),
+            score: 0.0,
+        })
+        .collect())
+}
+
+#[appsync_operation(query(searchPlayers))]
+async fn search_players(filter: Option<PlayerFilter>) -> Result<Vec<Player>, AppsyncError> {
+    let filter = filter.unwrap_or_default();
+    Ok(filter
+        .teams
+        .unwrap_or_default()
+        .into_iter()
+        .map(|team| Player {
+            id: ID::new(),
+            name: filter.name_contains.clone().unwrap_or_default(),
+            team,
+            score: filter.min_score.unwrap_or_default(),
+        })
+        .collect())
+}
+
+#[appsync_operation(mutation(createPlayer))]
+async fn create_player(input: PlayerInput) -> Result<Player, AppsyncError> {
+    Ok(Player {
+        id: ID::new(),
+        name: input.name,
+        team: input.team,
+        score: input.score.unwrap_or_default(),
+    })
+}
+
+#[tokio::test]
+async fn test_omitted_arguments_use_defaults() {
+    let response = Event::query("players").call(function_handler).await;
+    let players = response["data"].as_array().unwrap();
+    assert_eq!(players.len(), 3);
+    assert_eq!(players[0]["name"], "Player0");
+    assert_eq!(players[0]["team"], "RUST");
+}
+
+#[tokio::test]
+async fn test_null_arguments_are_kept() {
+    let response = Event::query("players")
+        .arguments(json!({"limit": null, "team": null, "name": null}))
+        .call(function_handler)
+        .await;
+    let players = response["data"].as_array().unwrap();
+    assert_eq!(players.len(), 3);
+    assert_eq!(players[2]["name"], "Player2");
+    assert_eq!(players[2]["team"], "PYTHON");
+
+    let response = Event::query("searchPlayers")
+        .arguments(json!({"filter": null}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_provided_arguments_override_defaults() {
+    let response = Event::query("players")
+        .arguments(json!({"limit": 1, "offset": 5, "team": "JS", "name": "Test"}))
+        .call(function_handler)
+        .await;
+    let players = response["data"].as_array().unwrap();
+    assert_eq!(players.len(), 1);
+    assert_eq!(players[0]["name"], "Test5");
+    assert_eq!(players[0]["team"], "JS");
+}
+
+#[tokio::test]
+async fn test_input_object_argument_default() {
+    let response = Event::query("searchPlayers").call(function_handler).await;
+    let players = response["data"].as_array().unwrap();
+    assert_eq!(players.len(), 2);
+    assert_eq!(players[0]["team"], "RUST");
+    assert_eq!(players[1]["team"], "JS");
+    assert_eq!(players[1]["score"], 1.5);
+}
+
+#[test]
+fn test_input_field_defaults() {
+    let input: PlayerInput = serde_json::from_value(json!({"name": "Test"})).unwrap();
+    assert_eq!(input.team, Team::Js);
+    assert_eq!(input.score, Some(0.0));
+
+    // Explicit values are kept
+    let input: PlayerInput =
+        serde_json::from_value(json!({"name": "Test", "team": "RUST", "score": null})).unwrap();
+    assert_eq!(input.team, Team::Rust);
+    assert_eq!(input.score, None);
+}
+
+#[test]
+fn test_input_default_impl() {
+    let filter = PlayerFilter::default();
+    assert_eq!(filter.teams, Some(vec![Team::Rust, Team::Python, Team::Js]));
+    assert_eq!(filter.min_score, Some(0.0));
+    assert_eq!(filter.name_contains, None);
+
+    let filter: PlayerFilter = serde_json::from_value(json!({"minScore": 2.5})).unwrap();
+    assert_eq!(filter.teams, Some(vec![Team::Rust, Team::Python, Team::Js]));
+    assert_eq!(filter.min_score, Some(2.5));
+}
+
+// The default values cannot be checked against the overridden types
+pub mod overridden {
+    lambda_appsync::appsync_lambda_main!(
+        "lambda-appsync-proc/tests/schemas/default_values.graphql",
+        only_appsync_types = true,
+        builders = true,
+        type_override = PlayerInput.score: String,
+    );
+}
+
+#[test]
+fn test_input_default_not_matching_override() {
+    let error = serde_json::from_value::<overridden::PlayerInput>(json!({"name": "Test"}))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.starts_with("The default value of field \"score\" does not match its type"),
+        "{error}"
+    );
+
+    let input: overridden::PlayerInput =
+        serde_json::from_value(json!({"name": "Test", "score": "high"})).unwrap();
+    assert_eq!(input.score.as_deref(), Some("high"));
+}
+
+#[test]
+fn test_unchecked_default_left_out_of_builder() {
+    let input = overridden::PlayerInput::builder().name("Test").build();
+    assert_eq!(input.team, overridden::Team::Js);
+    assert_eq!(input.score, None);
+}
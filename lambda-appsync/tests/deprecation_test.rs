@@ -0,0 +1,71 @@
+// The generated code must not trigger deprecation warnings by itself
+#![deny(deprecated)]
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::json;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/deprecations.graphql",
+    batch = false,
+);
+
+// Setting a deprecated field is itself a use of it
+#[allow(deprecated)]
+fn new_player(id: ID) -> Player {
+    Player {
+        id,
+        name: "Test Player".to_owned(),
+        nickname: None,
+        team: Team::Rust,
+    }
+}
+
+#[appsync_operation(query(player))]
+async fn get_player(id: ID) -> Result<Option<Player>, AppsyncError> {
+    Ok(Some(new_player(id)))
+}
+
+// Deprecated operations can still be implemented without `warn_deprecated_operations`
+#[appsync_operation(query(players))]
+async fn get_players() -> Result<Vec<Player>, AppsyncError> {
+    Ok(vec![])
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_items_serialization() {
+    let player = Player {
+        id: ID::new(),
+        name: "Test Player".to_owned(),
+        nickname: Some("Tester".to_owned()),
+        team: Team::Python,
+    };
+    let value = serde_json::to_value(&player).unwrap();
+    assert_eq!(value["nickname"], "Tester");
+    assert_eq!(value["team"], "PYTHON");
+    assert_eq!(Team::Python.to_string(), "PYTHON");
+    assert_eq!("PYTHON".parse::<Team>().unwrap(), Team::Python);
+    assert_eq!(Team::all(), [Team::Rust, Team::Python, Team::Js]);
+}
+
+#[tokio::test]
+async fn test_deprecated_operation_dispatch() {
+    let event = json!({
+        "info": {
+            "fieldName": "players",
+            "parentTypeName": "Query",
+            "variables": {},
+            "selectionSetList": ["id"],
+            "selectionSetGraphQL": "{id}"
+        },
+        "arguments": {},
+        "identity": null,
+        "request": null,
+        "source": null
+    });
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value["data"], json!([]));
+}
@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/derives.graphql",
+    batch = false,
+    derive_all = PartialEq,
+    derive = Player: Eq, Hash,
+    // Skipped as `Settings` gets a generated `Default` implementation
+    derive = PlayerFilter: Default,
+    derive = Settings: Default,
+    attr = Player: #[non_exhaustive],
+    attr = Team: #[allow(clippy::upper_case_acronyms)],
+);
+
+#[appsync_operation(query(players))]
+async fn get_players(
+    filter: Option<PlayerFilter>,
+    _settings: Option<Settings>,
+) -> Result<Vec<Player>, AppsyncError> {
+    let filter = filter.unwrap_or_default();
+    Ok(vec![Player {
+        id: ID::new(),
+        name: filter.name.unwrap_or_default(),
+        team: filter.team,
+    }])
+}
+
+#[appsync_operation(query(search))]
+async fn search(text: String) -> Result<Vec<SearchResult>, AppsyncError> {
+    Ok(vec![SearchResult::Game(Game {
+        id: ID::new(),
+        title: text,
+    })])
+}
+
+#[appsync_operation(query(characters))]
+async fn characters() -> Result<Vec<Character>, AppsyncError> {
+    Ok(vec![Character::Hero(Hero {
+        name: "Ferris".to_owned(),
+        power: "Memory safety".to_owned(),
+    })])
+}
+
+#[test]
+fn test_derive() {
+    let player = Player {
+        id: ID::new(),
+        name: "Ferris".to_owned(),
+        team: Some(Team::Rust),
+    };
+    let players = HashSet::from([player.clone(), player.clone()]);
+    assert_eq!(players.len(), 1);
+    assert!(players.contains(&player));
+}
+
+#[test]
+fn test_derive_all() {
+    let game = Game {
+        id: ID::new(),
+        title: "Crab Rave".to_owned(),
+    };
+    assert_eq!(
+        SearchResult::Game(game.clone()),
+        SearchResult::from(game.clone())
+    );
+    assert_ne!(
+        SearchResult::Game(game.clone()),
+        SearchResult::Game(Game {
+            title: "Crab Rave 2".to_owned(),
+            ..game
+        })
+    );
+
+    let hero = Hero {
+        name: "Ferris".to_owned(),
+        power: "Memory safety".to_owned(),
+    };
+    assert_eq!(Character::from(hero.clone()), Character::Hero(hero));
+
+    // Derives already generated by the macro are skipped
+    assert_eq!(
+        Settings::default(),
+        Settings {
+            limit: Some(10),
+            ascending: None
+        }
+    );
+    assert_eq!(
+        PlayerFilter::default(),
+        PlayerFilter {
+            name: None,
+            team: None
+        }
+    );
+}
+
+// Enums with deprecated variants are generated in a module, which sees the local imports
+pub mod deprecated_variants {
+    use names::TeamName;
+
+    lambda_appsync::appsync_lambda_main!(
+        "lambda-appsync-proc/tests/schemas/deprecations.graphql",
+        only_appsync_types = true,
+        attr = Team: #[serde(into = "TeamName")],
+    );
+
+    mod names {
+        #[derive(serde::Serialize)]
+        pub struct TeamName(&'static str);
+        impl From<super::Team> for TeamName {
+            #[allow(deprecated)]
+            fn from(team: super::Team) -> Self {
+                match team {
+                    super::Team::Rust => Self("Rust"),
+                    super::Team::Python => Self("Python"),
+                    super::Team::Js => Self("JavaScript"),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_attr_on_enum_with_deprecated_variants() {
+    assert_eq!(
+        serde_json::to_value(deprecated_variants::Team::Rust).unwrap(),
+        serde_json::json!("Rust")
+    );
+}
@@ -0,0 +1,166 @@
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, AppsyncError, AppsyncErrorKind, PartialData, ID,
+};
+use serde::Serialize;
+use serde_json::json;
+
+mod common;
+use common::Event;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/partial_updates.graphql",
+    batch = false,
+);
+
+#[derive(Debug, Serialize)]
+struct FieldError {
+    field: &'static str,
+    code: &'static str,
+}
+
+#[derive(Debug, thiserror::Error, AppsyncError)]
+enum PlayerError {
+    #[error("No player with the ID {0}")]
+    #[appsync(type = "NotFound")]
+    UnknownPlayer(ID),
+    #[error("Invalid update")]
+    InvalidUpdate {
+        reason: String,
+        #[appsync(info)]
+        details: Vec<FieldError>,
+    },
+    #[error("Unavailable")]
+    Unavailable,
+}
+
+#[derive(Debug, thiserror::Error, AppsyncError)]
+#[appsync(type = "Database")]
+enum DatabaseError {
+    #[error("Timeout after {0}ms")]
+    Timeout(#[appsync(info)] u64),
+    #[error("Connection lost")]
+    ConnectionLost,
+}
+
+#[derive(Debug, thiserror::Error, AppsyncError)]
+#[error("Quota of {limit} exceeded")]
+struct QuotaExceeded<T: Serialize> {
+    #[appsync(info)]
+    limit: T,
+}
+
+// Like `anyhow::Result<T>`, the error type of the handler can come from an alias
+type PlayerResult<T> = Result<T, PlayerError>;
+
+// Error types named like the `AppsyncError` of the crate are converted all the same
+mod api {
+    pub type AppsyncError = super::PlayerError;
+}
+
+#[appsync_operation(query(players))]
+async fn get_players(team: Option<Team>) -> Result<PartialData<Vec<Player>>, api::AppsyncError> {
+    match team {
+        Some(Team::Js) => Err(PlayerError::Unavailable),
+        _ => Ok(PartialData::new(vec![])),
+    }
+}
+
+#[appsync_operation(mutation(updatePlayer), keep_original_function_name)]
+async fn update_player(
+    id: ID,
+    update: PlayerUpdate,
+    reason: Option<String>,
+) -> PlayerResult<Player> {
+    let Some(name) = update.name else {
+        return Err(PlayerError::InvalidUpdate {
+            reason: reason.unwrap_or_default(),
+            details: vec![FieldError {
+                field: "name",
+                code: "E_MISSING",
+            }],
+        });
+    };
+    if name == "Ghost" {
+        Err(PlayerError::UnknownPlayer(id))
+    } else {
+        Ok(Player {
+            id,
+            name,
+            nickname: None,
+            team: None,
+        })
+    }
+}
+
+#[test]
+fn test_derived_error_kind() {
+    let error = PlayerError::Unavailable;
+    assert_eq!(error.error_type(), "Unavailable");
+    assert_eq!(error.error_info(), None);
+
+    let error = AppsyncError::from(DatabaseError::Timeout(500));
+    assert_eq!(error.error_type, "Database");
+    assert_eq!(error.error_message, "Timeout after 500ms");
+    assert_eq!(error.error_info, Some(json!(500)));
+    assert_eq!(DatabaseError::ConnectionLost.error_type(), "Database");
+
+    let error = AppsyncError::from(QuotaExceeded { limit: 10 });
+    assert_eq!(error.error_type, "QuotaExceeded");
+    assert_eq!(error.error_message, "Quota of 10 exceeded");
+    assert_eq!(error.error_info, Some(json!(10)));
+}
+
+#[tokio::test]
+async fn test_operation_custom_error() {
+    let id = ID::new().to_string();
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {"name": "Ghost"}}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "NotFound");
+    assert_eq!(
+        response["errorMessage"],
+        format!("No player with the ID {id}")
+    );
+
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {}}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "InvalidUpdate");
+    assert_eq!(
+        response["errorInfo"],
+        json!([{"field": "name", "code": "E_MISSING"}])
+    );
+
+    // Errors of the arguments are still reported
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "InvalidArgs");
+
+    let response = Event::query("players")
+        .arguments(json!({"team": "JS"}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "Unavailable");
+    let response = Event::query("players").call(function_handler).await;
+    assert_eq!(response["data"], json!([]));
+
+    // The original function keeps its error type
+    let error = update_player(
+        ID::new(),
+        PlayerUpdate {
+            name: None,
+            nickname: None,
+            team: None,
+            tags: None,
+            mentor: None,
+        },
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(error, PlayerError::InvalidUpdate { .. }));
+}
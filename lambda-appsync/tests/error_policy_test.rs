@@ -0,0 +1,102 @@
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, AppsyncResponse,
+    PartialData, PartialResult, ID,
+};
+use serde_json::json;
+
+mod common;
+use common::Event;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/partial_updates.graphql",
+    batch = false,
+    hook = reject_banned,
+    error_policy = sanitize("NotFound", "ValidationError"),
+);
+
+async fn reject_banned(event: &AppsyncEvent<Operation>) -> Option<AppsyncResponse> {
+    event
+        .request
+        .header("x-banned")
+        .map(|_| AppsyncError::new("Banned", "This client is banned").into())
+}
+
+#[appsync_operation(query(players))]
+async fn get_players(team: Option<Team>) -> PartialResult<Vec<Player>> {
+    let error = AppsyncError::new(
+        "ResourceNotFoundException",
+        "Requested resource not found: arn:aws:dynamodb:eu-west-1:123456789012:table/players",
+    );
+    match team {
+        Some(Team::Rust) => Ok(PartialData::new(vec![]).with_error(error)),
+        _ => Err(error),
+    }
+}
+
+#[appsync_operation(mutation(updatePlayer))]
+async fn update_player(
+    id: ID,
+    update: PlayerUpdate,
+    _reason: Option<String>,
+) -> Result<Player, AppsyncError> {
+    match update.name {
+        Some(name) if !name.is_empty() => Err(AppsyncError::new(
+            "NotFound",
+            format!("No player with the ID {id}"),
+        )),
+        _ => Err(AppsyncError::new("ValidationError", "Invalid update")
+            | AppsyncError::new("NotFound", "Unknown player")),
+    }
+}
+
+#[tokio::test]
+async fn test_sanitized_errors() {
+    let response = Event::query("players").call(function_handler).await;
+    assert_eq!(response["data"], json!(null));
+    assert_eq!(response["errorType"], "InternalError");
+    let correlation_id = response["errorInfo"]["correlationId"].as_str().unwrap();
+    assert!(response["errorMessage"]
+        .as_str()
+        .unwrap()
+        .contains(correlation_id));
+    assert!(!response.to_string().contains("arn:aws"));
+
+    // The data of a partial result is kept
+    let response = Event::query("players")
+        .arguments(json!({"team": "RUST"}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"], json!([]));
+    assert_eq!(response["errorType"], "InternalError");
+
+    // Invalid arguments are operation errors too
+    let response = Event::mutation("updatePlayer").call(function_handler).await;
+    assert_eq!(response["errorType"], "InternalError");
+}
+
+#[tokio::test]
+async fn test_allowed_errors() {
+    let id = ID::new().to_string();
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {"name": "Ferris"}}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "NotFound");
+    assert_eq!(
+        response["errorMessage"],
+        format!("No player with the ID {id}")
+    );
+
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {}}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "ValidationError|NotFound");
+
+    // Responses of the hook are not operation errors
+    let response = Event::query("players")
+        .headers(json!({"X-Banned": "1"}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "Banned");
+}
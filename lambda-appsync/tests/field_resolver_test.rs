@@ -0,0 +1,149 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, ID};
+use serde_json::json;
+
+// Generate AppSync types and runtime with resolvers on `Player` fields
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/field_resolvers.graphql",
+    batch = false,
+    field_resolver = Player.stats,
+    field_resolver = Player.teammates,
+    // Non-null, and absent from the `source`
+    field_resolver = Player.rank,
+    // The parent type can be renamed, its references must then be overridden
+    name_override = Player: Athlete,
+    type_override = Query.player: Athlete,
+    type_override = Player.teammates: Athlete,
+    // Field arguments can be overridden like operation arguments
+    type_override = Player.stats.season: u16,
+);
+
+#[appsync_operation(query(player))]
+async fn get_player(id: ID) -> Result<Option<Athlete>, AppsyncError> {
+    Ok(Some(Athlete {
+        id,
+        name: "Test Player".to_owned(),
+        stats: None,
+        teammates: None,
+        rank: None,
+    }))
+}
+
+#[appsync_operation(field(Player.stats))]
+async fn player_stats(
+    player: Athlete,
+    season: Option<u16>,
+) -> Result<Option<PlayerStats>, AppsyncError> {
+    if player.name.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(PlayerStats {
+        season: season.unwrap_or(2024) as i32,
+        goals: player.name.len() as i32,
+    }))
+}
+
+#[appsync_operation(field(Player.rank))]
+async fn player_rank(player: Athlete) -> Result<i32, AppsyncError> {
+    Ok(player.name.len() as i32)
+}
+
+fn field_event(
+    field_name: &str,
+    source: serde_json::Value,
+    arguments: serde_json::Value,
+) -> serde_json::Value {
+    json!({
+        "info": {
+            "fieldName": field_name,
+            "parentTypeName": "Player",
+            "variables": {},
+            "selectionSetList": ["season", "goals"],
+            "selectionSetGraphQL": "{season goals}"
+        },
+        "arguments": arguments,
+        "identity": null,
+        "request": null,
+        "source": source
+    })
+}
+
+#[tokio::test]
+async fn test_field_resolver_parent_and_args() {
+    let event = field_event(
+        "stats",
+        json!({"id": ID::new().to_string(), "name": "Test Player"}),
+        json!({"season": 2023}),
+    );
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value["data"], json!({"season": 2023, "goals": 11}));
+}
+
+#[tokio::test]
+async fn test_field_resolver_non_null_field_not_in_source() {
+    let event = field_event(
+        "rank",
+        json!({"id": ID::new().to_string(), "name": "Test Player"}),
+        json!({}),
+    );
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value["data"], json!(11));
+}
+
+#[tokio::test]
+async fn test_field_resolver_missing_optional_arg() {
+    let event = field_event(
+        "stats",
+        json!({"id": ID::new().to_string(), "name": ""}),
+        json!({}),
+    );
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert!(response_value["data"].is_null());
+    assert!(response_value.get("errorType").is_none());
+}
+
+#[tokio::test]
+async fn test_field_resolver_invalid_source() {
+    let event = field_event("stats", json!({"id": 42}), json!({}));
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value["errorType"], "InvalidSource");
+}
+
+#[tokio::test]
+async fn test_field_resolver_unimplemented() {
+    let event = field_event(
+        "teammates",
+        json!({"id": ID::new().to_string(), "name": "Test Player"}),
+        json!({"first": 3}),
+    );
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    assert_eq!(response_value["errorType"], "Unimplemented");
+    assert_eq!(
+        response_value["errorMessage"],
+        "Field `Player.teammates` is unimplemented"
+    );
+}
+
+#[test]
+fn test_field_resolver_operation_deserialization() {
+    let event: AppsyncEvent<Operation> =
+        serde_json::from_value(field_event("teammates", json!(null), json!({}))).unwrap();
+    assert!(matches!(
+        event.info.operation,
+        Operation::Athlete(AthleteField::Teammates)
+    ));
+}
@@ -0,0 +1,142 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, Maybe, ID};
+use serde_json::json;
+
+mod common;
+use common::Event;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/partial_updates.graphql",
+    batch = false,
+    nullable_inputs_as_maybe = true,
+);
+
+#[appsync_operation(query(players))]
+async fn get_players(team: Maybe<Team>) -> Result<Vec<Player>, AppsyncError> {
+    let player = |team| Player {
+        id: ID::new(),
+        name: "Test Player".to_owned(),
+        nickname: None,
+        team,
+    };
+    Ok(match team {
+        // No filter
+        Maybe::Undefined => vec![player(Some(Team::Rust)), player(None)],
+        // Players without a team
+        Maybe::Null => vec![player(None)],
+        Maybe::Value(team) => vec![player(Some(team))],
+    })
+}
+
+#[appsync_operation(mutation(updatePlayer))]
+async fn update_player(
+    id: ID,
+    update: PlayerUpdate,
+    reason: Maybe<String>,
+) -> Result<Player, AppsyncError> {
+    if reason.is_null() {
+        return Err(AppsyncError::new(
+            "InvalidArgs",
+            "The reason cannot be null",
+        ));
+    }
+    let mut player = Player {
+        id,
+        name: "Test Player".to_owned(),
+        nickname: Some("Ferris".to_owned()),
+        team: Some(Team::Rust),
+    };
+    match update.name {
+        Maybe::Null => return Err(AppsyncError::new("InvalidArgs", "A name is required")),
+        Maybe::Value(name) => player.name = name,
+        Maybe::Undefined => (),
+    }
+    update.nickname.update(&mut player.nickname);
+    update.team.update(&mut player.team);
+    Ok(player)
+}
+
+#[tokio::test]
+async fn test_maybe_argument() {
+    let response = Event::query("players").call(function_handler).await;
+    assert_eq!(response["data"].as_array().unwrap().len(), 2);
+
+    let response = Event::query("players")
+        .arguments(json!({"team": null}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"][0]["team"], json!(null));
+
+    let response = Event::query("players")
+        .arguments(json!({"team": "JS"}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"][0]["team"], "JS");
+}
+
+#[tokio::test]
+async fn test_maybe_input_fields() {
+    let id = ID::new().to_string();
+
+    // Omitted fields are left untouched
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {"name": "New Name"}}))
+        .call(function_handler)
+        .await;
+    assert_eq!(
+        response["data"],
+        json!({"id": id, "name": "New Name", "nickname": "Ferris", "team": "RUST"})
+    );
+
+    // Null fields are cleared
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {"nickname": null, "team": "PYTHON"}}))
+        .call(function_handler)
+        .await;
+    assert_eq!(
+        response["data"],
+        json!({"id": id, "name": "Test Player", "team": "PYTHON"})
+    );
+
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {"name": null}}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "InvalidArgs");
+
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {}, "reason": null}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "InvalidArgs");
+}
+
+#[test]
+fn test_maybe_input_serde() {
+    let update: PlayerUpdate = serde_json::from_value(json!({
+        "nickname": null,
+        "mentor": {"name": "Mentor"}
+    }))
+    .unwrap();
+    assert!(update.name.is_undefined());
+    assert!(update.nickname.is_null());
+    assert!(update.team.is_undefined());
+    // The default value of a field is still applied when it is omitted
+    assert_eq!(update.tags, Maybe::Value(vec![]));
+    assert!(matches!(
+        update.mentor().map(|mentor| &mentor.name),
+        Maybe::Value(Maybe::Value(name)) if name == "Mentor"
+    ));
+
+    // Undefined fields are skipped, null fields are kept
+    assert_eq!(
+        serde_json::to_value(&update).unwrap(),
+        json!({
+            "nickname": null,
+            "tags": [],
+            "mentor": {"name": "Mentor", "tags": []}
+        })
+    );
+
+    // Input types with only nullable fields implement `Default`
+    assert!(PlayerUpdate::default().name.is_undefined());
+}
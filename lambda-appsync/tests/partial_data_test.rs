@@ -0,0 +1,91 @@
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, PartialData, PartialResult,
+    ID,
+};
+use serde_json::json;
+
+mod common;
+use common::Event;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/partial_updates.graphql",
+    batch = false,
+    check_responses = true,
+);
+
+fn player(name: &str) -> Player {
+    Player {
+        id: ID::new(),
+        name: name.to_owned(),
+        nickname: None,
+        team: None,
+    }
+}
+
+// Handlers can return an alias of `PartialResult`
+type Players = PartialResult<Vec<Player>>;
+
+#[appsync_operation(query(players), with_appsync_event)]
+async fn get_players(team: Option<Team>, _event: &AppsyncEvent<Operation>) -> Players {
+    match team {
+        None => Ok(PartialData::new(vec![player("Ferris")])),
+        Some(Team::Rust) => Ok(PartialData::new(vec![player("Ferris")]).with_error(
+            AppsyncError::new("PartialFailure", "Some players could not be read")
+                .with_error_info(json!({"unreadable": 2})),
+        )),
+        Some(_) => Err(AppsyncError::new("Unavailable", "Team unavailable")),
+    }
+}
+
+#[appsync_operation(mutation(updatePlayer))]
+async fn update_player(
+    _id: ID,
+    update: PlayerUpdate,
+    _reason: Option<String>,
+) -> Result<Player, AppsyncError> {
+    match update.name {
+        Some(name) if !name.is_empty() => Ok(player(&name)),
+        _ => Err(AppsyncError::new("ValidationError", "Invalid update")
+            .with_error_info_entry("code", "E_EMPTY_NAME")
+            .with_error_info_entry("fields", json!(["name"]))),
+    }
+}
+
+#[tokio::test]
+async fn test_partial_data() {
+    let response = Event::query("players").call(function_handler).await;
+    assert_eq!(response["data"][0]["name"], "Ferris");
+    assert!(response.get("errorType").is_none());
+
+    let response = Event::query("players")
+        .arguments(json!({"team": "RUST"}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"][0]["name"], "Ferris");
+    assert_eq!(response["errorType"], "PartialFailure");
+    assert_eq!(response["errorMessage"], "Some players could not be read");
+    assert_eq!(response["errorInfo"], json!({"unreadable": 2}));
+
+    let response = Event::query("players")
+        .arguments(json!({"team": "JS"}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"], json!(null));
+    assert_eq!(response["errorType"], "Unavailable");
+    assert!(response.get("errorInfo").is_none());
+}
+
+#[tokio::test]
+async fn test_error_info() {
+    let id = ID::new().to_string();
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {"name": ""}}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"], json!(null));
+    assert_eq!(response["errorType"], "ValidationError");
+    assert_eq!(
+        response["errorInfo"],
+        json!({"code": "E_EMPTY_NAME", "fields": ["name"]})
+    );
+}
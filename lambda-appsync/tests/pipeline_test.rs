@@ -0,0 +1,109 @@
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, Maybe, ID,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+mod common;
+use common::Event;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/partial_updates.graphql",
+    batch = false,
+    nullable_inputs_as_maybe = true,
+);
+
+#[derive(Deserialize)]
+struct Stash {
+    nickname: Option<String>,
+}
+
+#[appsync_operation(query(players), with_stash)]
+async fn get_players(team: Maybe<Team>, stash: Stash) -> Result<Vec<Player>, AppsyncError> {
+    Ok(vec![Player {
+        id: ID::new(),
+        name: "Test Player".to_owned(),
+        nickname: stash.nickname,
+        team: team.into_option(),
+    }])
+}
+
+#[appsync_operation(
+    mutation(updatePlayer),
+    with_stash,
+    with_prev_result,
+    with_appsync_event,
+    keep_original_function_name
+)]
+async fn update_player(
+    id: ID,
+    update: PlayerUpdate,
+    _reason: Maybe<String>,
+    stash: Option<Stash>,
+    prev_result: Option<Player>,
+    event: &AppsyncEvent<Operation>,
+) -> Result<Player, AppsyncError> {
+    assert!(matches!(
+        event.info.operation,
+        Operation::Mutation(MutationField::UpdatePlayer)
+    ));
+    let mut player = prev_result.ok_or_else(|| AppsyncError::new("NotFound", "No player"))?;
+    player.id = id;
+    if let Maybe::Value(name) = update.name {
+        player.name = name;
+    }
+    if let Some(stash) = stash {
+        player.nickname = stash.nickname;
+    }
+    Ok(player)
+}
+
+#[tokio::test]
+async fn test_typed_stash() {
+    let response = Event::query("players")
+        .arguments(json!({"team": "RUST"}))
+        .stash(json!({"nickname": "Ferris"}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"][0]["nickname"], "Ferris");
+    assert_eq!(response["data"][0]["team"], "RUST");
+
+    let response = Event::query("players")
+        .stash(json!({"nickname": 42}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "InvalidStash");
+}
+
+#[tokio::test]
+async fn test_typed_prev_result() {
+    let id = ID::new().to_string();
+    let prev = json!({
+        "result": {"id": ID::new(), "name": "Test Player", "nickname": null, "team": "JS"}
+    });
+
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {"name": "New Name"}}))
+        .stash(json!({"nickname": "Ferris"}))
+        .prev(prev.clone())
+        .call(function_handler)
+        .await;
+    assert_eq!(
+        response["data"],
+        json!({"id": id, "name": "New Name", "nickname": "Ferris", "team": "JS"})
+    );
+
+    // Without a stash nor a previous function
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {}}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "NotFound");
+
+    let response = Event::mutation("updatePlayer")
+        .arguments(json!({"id": id, "update": {}}))
+        .prev(json!({"result": [1, 2]}))
+        .call(function_handler)
+        .await;
+    assert_eq!(response["errorType"], "InvalidPrevResult");
+}
@@ -0,0 +1,143 @@
+use lambda_appsync::{appsync_lambda_main, appsync_operation, AppsyncError, ID};
+use serde_json::json;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/recursive_types.graphql",
+    batch = false,
+);
+
+fn new_thread(id: ID) -> Thread {
+    Thread {
+        id,
+        first_comment: None,
+        pinned: None,
+    }
+}
+
+#[appsync_operation(query(comment))]
+async fn get_comment(id: ID) -> Result<Option<Comment>, AppsyncError> {
+    Ok(Some(Comment {
+        id,
+        text: "Reply".to_owned(),
+        parent: Some(Box::new(Comment {
+            id: ID::new(),
+            text: "Root".to_owned(),
+            parent: None,
+            replies: vec![],
+            thread: Box::new(new_thread(ID::new())),
+            author: None,
+        })),
+        replies: vec![],
+        thread: Box::new(new_thread(ID::new())),
+        author: Some(Author {
+            name: "Author".to_owned(),
+        }),
+    }))
+}
+
+#[appsync_operation(query(searchComments))]
+async fn search_comments(filter: CommentFilter) -> Result<Vec<Comment>, AppsyncError> {
+    let mut texts = vec![];
+    let mut filter = Some(&filter);
+    while let Some(f) = filter {
+        texts.extend(f.text.clone());
+        filter = f.and();
+    }
+    Ok(texts
+        .into_iter()
+        .map(|text| Comment {
+            id: ID::new(),
+            text,
+            parent: None,
+            replies: vec![],
+            thread: Box::new(new_thread(ID::new())),
+            author: None,
+        })
+        .collect())
+}
+
+#[test]
+fn test_recursive_types_round_trip() {
+    let value = json!({
+        "id": "11111111-1111-1111-1111-111111111111",
+        "text": "Reply",
+        "parent": {
+            "id": "22222222-2222-2222-2222-222222222222",
+            "text": "Root",
+            "replies": [],
+            "thread": {"id": "33333333-3333-3333-3333-333333333333"}
+        },
+        "replies": [],
+        "thread": {
+            "id": "33333333-3333-3333-3333-333333333333",
+            "firstComment": {"id": "22222222-2222-2222-2222-222222222222", "text": "Root", "replies": [], "thread": {"id": "33333333-3333-3333-3333-333333333333"}},
+            "pinned": {"__typename": "Thread", "id": "44444444-4444-4444-4444-444444444444"}
+        }
+    });
+    let comment: Comment = serde_json::from_value(value).unwrap();
+
+    // Accessors borrow the boxed values
+    let parent = comment.parent().unwrap();
+    assert_eq!(parent.text, "Root");
+    assert!(parent.parent().is_none());
+    let thread = comment.thread();
+    assert_eq!(thread.first_comment().unwrap().text, "Root");
+    assert!(matches!(thread.pinned(), Some(Post::Thread(_))));
+
+    let value = serde_json::to_value(&comment).unwrap();
+    assert_eq!(value["parent"]["text"], "Root");
+    assert_eq!(value["thread"]["pinned"]["__typename"], "Thread");
+}
+
+#[test]
+fn test_interface_fields_are_boxed_consistently() {
+    let answer = Answer {
+        parent: Some(Box::new(Comment {
+            id: ID::new(),
+            text: "Question".to_owned(),
+            parent: None,
+            replies: vec![],
+            thread: Box::new(new_thread(ID::new())),
+            author: None,
+        })),
+        accepted: true,
+    };
+    assert_eq!(answer.parent().unwrap().text, "Question");
+    // The boxed field accessor and the interface accessor borrow the same value
+    let inherent: Option<&Comment> = answer.parent();
+    let interface: Option<&Comment> = ReplyFields::parent(&answer);
+    assert!(std::ptr::eq(inherent.unwrap(), interface.unwrap()));
+
+    let reply: Reply = answer.into();
+    assert_eq!(ReplyFields::parent(&reply).unwrap().text, "Question");
+}
+
+#[tokio::test]
+async fn test_recursive_input() {
+    let event = json!({
+        "info": {
+            "fieldName": "searchComments",
+            "parentTypeName": "Query",
+            "variables": {},
+            "selectionSetList": ["text"],
+            "selectionSetGraphQL": "{text}"
+        },
+        "arguments": {
+            "filter": {"text": "a", "and": {"text": "b", "and": {"text": "c"}}}
+        },
+        "identity": null,
+        "request": null,
+        "source": null
+    });
+    let lambda_event = lambda_runtime::LambdaEvent::new(event, Default::default());
+    let response = function_handler(lambda_event).await.unwrap();
+
+    let response_value = serde_json::to_value(response).unwrap();
+    let texts = response_value["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["text"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(texts, ["a", "b", "c"]);
+}
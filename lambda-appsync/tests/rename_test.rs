@@ -13,6 +13,12 @@ lambda_appsync::appsync_lambda_main!("schema.graphql",
     name_override = WeirdFieldNames.await: no_await,
     name_override = WeirdFieldNames.crate: no_crate,
     name_override = WeirdFieldNames.u8: no_u8,
+    // Override union name and one of its variants
+    name_override = Staff: StaffMember,
+    name_override = Staff.Referee: Official,
+    // Override an interface field accessor and the matching struct field
+    name_override = Person.name: full_name,
+    name_override = Referee.name: display_name,
 
     // MUST also override ALL the operation return type !!!
     type_override = Query.players: NewPlayer,
@@ -180,3 +186,43 @@ fn test_invalid_deserialization() {
     }));
     assert!(result.is_err());
 }
+
+#[test]
+fn test_union_rename() {
+    let referee_id = lambda_appsync::ID::new();
+    let staff = StaffMember::Official(Referee {
+        id: referee_id,
+        display_name: "Test Referee".to_string(),
+    });
+
+    // The `__typename` still uses the original GraphQL name
+    let json = serde_json::to_value(&staff).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "__typename": "Referee",
+            "id": referee_id,
+            "name": "Test Referee"
+        })
+    );
+
+    let staff: StaffMember = serde_json::from_value(json).unwrap();
+    assert!(matches!(staff, StaffMember::Official(_)));
+}
+
+#[test]
+fn test_interface_rename() {
+    let referee = Referee {
+        id: lambda_appsync::ID::new(),
+        display_name: "Test Referee".to_string(),
+    };
+    // The accessor is renamed and bound to the renamed struct field
+    assert_eq!(referee.full_name(), "Test Referee");
+
+    let person = Person::from(referee);
+    assert_eq!(person.full_name(), "Test Referee");
+    assert_eq!(
+        serde_json::to_value(&person).unwrap()["name"],
+        json!("Test Referee")
+    );
+}
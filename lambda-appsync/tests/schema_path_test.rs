@@ -0,0 +1,45 @@
+// Schema paths relative to the crate directory, or built from environment variables
+
+// Relative to the directory of this crate
+pub mod manifest_relative {
+    lambda_appsync::appsync_lambda_main!(
+        "../lambda-appsync-proc/tests/schemas/default_values.graphql",
+        only_appsync_types = true,
+    );
+}
+
+// Built with the `env!` and `concat!` macros
+pub mod env_macro {
+    lambda_appsync::appsync_lambda_main!(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../lambda-appsync-proc/tests/schemas/default_values.graphql"
+        ),
+        only_appsync_types = true,
+    );
+}
+
+// With environment variables in a list of paths
+pub mod env_var {
+    lambda_appsync::appsync_lambda_main!(
+        [
+            "$CARGO_MANIFEST_DIR/../lambda-appsync-proc/tests/schemas/split/base.graphql",
+            "${CARGO_MANIFEST_DIR}/../lambda-appsync-proc/tests/schemas/split/players.graphql",
+        ],
+        only_appsync_types = true,
+    );
+}
+
+#[test]
+fn test_schema_paths() {
+    assert_eq!(
+        manifest_relative::Team::all(),
+        [
+            manifest_relative::Team::Rust,
+            manifest_relative::Team::Python,
+            manifest_relative::Team::Js
+        ]
+    );
+    assert_eq!(env_macro::Team::COUNT, 3);
+    assert_eq!(env_var::Team::COUNT, 3);
+}
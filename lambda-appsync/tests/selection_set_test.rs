@@ -0,0 +1,114 @@
+use lambda_appsync::{
+    appsync_lambda_main, appsync_operation, AppsyncError, AppsyncEvent, SelectableField,
+    SelectedFields, SelectionSet, ID,
+};
+use serde_json::json;
+
+mod common;
+use common::Event;
+
+appsync_lambda_main!(
+    "lambda-appsync-proc/tests/schemas/field_resolvers.graphql",
+    batch = false,
+);
+
+// Schema with fields named after Rust keywords
+pub mod weird {
+    lambda_appsync::appsync_lambda_main!("schema.graphql", only_appsync_types = true);
+}
+
+#[appsync_operation(query(player), with_appsync_event)]
+async fn get_player(
+    id: ID,
+    event: &AppsyncEvent<Operation>,
+) -> Result<Option<Player>, AppsyncError> {
+    let fields = Player::selected_fields(event);
+    // Only load the stats when the client asked for them
+    let stats = fields.is_selected(PlayerFieldName::Stats).then(|| {
+        let stats_fields = fields.stats();
+        PlayerStats {
+            season: 2025,
+            goals: if stats_fields.is_selected(PlayerStatsFieldName::Goals) {
+                42
+            } else {
+                0
+            },
+        }
+    });
+    Ok(Some(Player {
+        id,
+        name: fields
+            .iter()
+            .map(PlayerFieldName::name)
+            .collect::<Vec<_>>()
+            .join(","),
+        stats,
+        teammates: None,
+        rank: 1,
+    }))
+}
+
+#[tokio::test]
+async fn test_selected_fields() {
+    let response = Event::query("player")
+        .arguments(json!({"id": ID::new()}))
+        .selection_set(&["id", "name"])
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"]["name"], "id,name");
+    assert_eq!(response["data"]["stats"], json!(null));
+
+    let response = Event::query("player")
+        .arguments(json!({"id": ID::new()}))
+        .selection_set(&["name", "stats", "stats/goals"])
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"]["name"], "name,stats");
+    assert_eq!(response["data"]["stats"]["goals"], 42);
+
+    let response = Event::query("player")
+        .arguments(json!({"id": ID::new()}))
+        .selection_set(&["stats", "stats/season"])
+        .call(function_handler)
+        .await;
+    assert_eq!(response["data"]["stats"]["goals"], 0);
+}
+
+#[test]
+fn test_nested_selected_fields() {
+    let selection_set: SelectionSet = ["teammates", "teammates/name", "stats"]
+        .into_iter()
+        .collect();
+    let fields = SelectedFields::<PlayerFieldName>::new(selection_set);
+    let teammates_fields: SelectedFields<PlayerFieldName> = fields.teammates();
+    assert_eq!(
+        teammates_fields.iter().collect::<Vec<_>>(),
+        [PlayerFieldName::Name]
+    );
+    let stats_fields: SelectedFields<PlayerStatsFieldName> = fields.stats();
+    assert_eq!(stats_fields.iter().count(), 0);
+}
+
+#[test]
+fn test_field_names() {
+    assert_eq!(
+        PlayerFieldName::ALL,
+        [
+            PlayerFieldName::Id,
+            PlayerFieldName::Name,
+            PlayerFieldName::Stats,
+            PlayerFieldName::Teammates,
+            PlayerFieldName::Rank
+        ]
+    );
+    assert_eq!(PlayerStatsFieldName::Goals.name(), "goals");
+}
+
+#[test]
+fn test_weird_field_name_variants() {
+    use weird::WeirdFieldNamesFieldName;
+
+    assert_eq!(WeirdFieldNamesFieldName::As.name(), "as");
+    assert_eq!(WeirdFieldNamesFieldName::RSelf.name(), "self");
+    assert_eq!(WeirdFieldNamesFieldName::Crate.name(), "crate");
+}
@@ -192,3 +192,100 @@ fn test_null_handling() {
     let optional_team = OptionalTeam { team: None };
     assert_eq!(serde_json::to_value(optional_team).unwrap(), json!({}));
 }
+
+#[test]
+fn test_union_serialization() {
+    let coach_id = lambda_appsync::ID::new();
+    let staff = Staff::from(Coach {
+        id: coach_id,
+        name: "Test Coach".to_string(),
+        team: Team::Rust,
+    });
+
+    let json = serde_json::to_value(&staff).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "__typename": "Coach",
+            "id": coach_id,
+            "name": "Test Coach",
+            "team": "RUST"
+        })
+    );
+}
+
+#[test]
+fn test_union_deserialization() {
+    let referee_id = lambda_appsync::ID::new();
+    let json = json!({
+        "__typename": "Referee",
+        "id": referee_id,
+        "name": "Test Referee"
+    });
+
+    let staff: Staff = serde_json::from_value(json).unwrap();
+    match staff {
+        Staff::Referee(referee) => {
+            assert_eq!(referee.id, referee_id);
+            assert_eq!(referee.name, "Test Referee");
+        }
+        _ => panic!("Expected Referee variant"),
+    }
+
+    // Missing or unknown `__typename` must be rejected
+    let result: Result<Staff, _> = serde_json::from_value(json!({
+        "id": referee_id,
+        "name": "Test Referee"
+    }));
+    assert!(result.is_err());
+    let result: Result<Staff, _> = serde_json::from_value(json!({
+        "__typename": "Player",
+        "id": referee_id,
+        "name": "Test Referee"
+    }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_interface_serialization() {
+    let coach_id = lambda_appsync::ID::new();
+    let person = Person::from(Coach {
+        id: coach_id,
+        name: "Test Coach".to_string(),
+        team: Team::Rust,
+    });
+
+    // Accessors are available on the interface enum
+    assert_eq!(person.id(), &coach_id);
+    assert_eq!(person.name(), "Test Coach");
+
+    let json = serde_json::to_value(&person).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "__typename": "Coach",
+            "id": coach_id,
+            "name": "Test Coach",
+            "team": "RUST"
+        })
+    );
+
+    let person: Person = serde_json::from_value(json).unwrap();
+    assert!(matches!(person, Person::Coach(_)));
+}
+
+#[test]
+fn test_interface_fields_trait() {
+    fn display_name(person: &impl PersonFields) -> String {
+        format!("{} ({})", person.name(), person.id())
+    }
+
+    let referee = Referee {
+        id: lambda_appsync::ID::new(),
+        name: "Test Referee".to_string(),
+    };
+    assert_eq!(
+        display_name(&referee),
+        format!("Test Referee ({})", referee.id)
+    );
+}